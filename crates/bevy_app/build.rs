@@ -0,0 +1,27 @@
+use std::{env, process::Command};
+
+// Records the compiler that built this crate so dynamically loaded plugins can be checked against the host.
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("-vV")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+
+    let field = |name: &str| {
+        version
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(|value| value.trim().to_string())
+    };
+    let release = field("release:").unwrap_or_else(|| "unknown".to_string());
+    let commit_hash = field("commit-hash:").unwrap_or_else(|| "unknown".to_string());
+
+    println!(
+        "cargo:rustc-env=BEVY_RUSTC_VERSION={} ({})",
+        release, commit_hash
+    );
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
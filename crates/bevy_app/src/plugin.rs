@@ -12,3 +12,21 @@ pub trait Plugin: Any + Send + Sync {
 }
 
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
+
+/// The Bevy version this crate was compiled as. Dynamic plugins must be built against the same version as the host.
+pub const BEVY_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The version and commit hash of the compiler this crate was built with. Rust has no stable ABI, so dynamic plugins
+/// must be built with the same compiler as the host.
+pub const RUSTC_VERSION: &str = env!("BEVY_RUSTC_VERSION");
+
+/// A nul-terminated string identifying the plugin ABI, made up of [BEVY_VERSION] and [RUSTC_VERSION].
+/// Exported by [DynamicPlugin](crate::DynamicPlugin) types through the [GetPluginAbiVersion] function.
+pub const DYNAMIC_PLUGIN_ABI_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " / rustc ",
+    env!("BEVY_RUSTC_VERSION"),
+    "\0"
+);
+
+pub type GetPluginAbiVersion = unsafe extern "C" fn() -> *const std::os::raw::c_char;
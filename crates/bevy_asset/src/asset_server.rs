@@ -1,16 +1,32 @@
 use crate::{
-    path::{AssetPath, AssetPathId, SourcePathId},
+    get_meta_path,
+    loader::LoadContextSettings,
+    loading_tracker::CountingReader,
+    path::{get_hasher, AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetProcessor, AssetReader, AssetSaver, AssetSaverDynamic, Assets, Handle,
+    HandleId, HandleUntyped, LabelId, LoadContext, LoadState, ProcessedMeta,
+    RecursiveDependencyLoadState, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
-use bevy_ecs::Res;
+use bevy_app::prelude::Events;
+use bevy_ecs::{Res, ResMut};
 use bevy_tasks::TaskPool;
-use bevy_utils::HashMap;
-use crossbeam_channel::TryRecvError;
+use bevy_type_registry::TypeUuid;
+use bevy_utils::{HashMap, HashSet};
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
+use futures_lite::io::Cursor;
 use parking_lot::RwLock;
-use std::{collections::hash_map::Entry, path::Path, sync::Arc};
+use serde::Serialize;
+use std::{
+    collections::hash_map::Entry,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -27,6 +43,39 @@ pub enum AssetServerError {
     AssetLoaderError(anyhow::Error),
     #[error("PathLoader encountered an error")]
     PathLoaderError(#[from] AssetIoError),
+    #[error("No AssetIo is registered for the asset source.")]
+    MissingAssetSource(String),
+    #[error("Encountered an error while processing an asset.")]
+    AssetProcessorError(anyhow::Error),
+    #[error("The asset does not exist.")]
+    MissingAsset(HandleId),
+    #[error("No AssetSaver found for the asset type and extension.")]
+    MissingAssetSaver(Option<String>),
+    #[error("Encountered an error while saving an asset.")]
+    AssetSaverError(anyhow::Error),
+    #[error("The loader settings of {path:?} do not match its loader: {error}")]
+    InvalidLoaderSettings { path: PathBuf, error: anyhow::Error },
+}
+
+/// Sent when an asset fails to load
+#[derive(Debug, Clone)]
+pub struct AssetLoadFailed {
+    pub path: AssetPath<'static>,
+    /// A weak handle to the asset that failed to load
+    pub handle: HandleUntyped,
+    pub error: Arc<AssetServerError>,
+}
+
+pub(crate) struct LoadFailedChannel {
+    sender: Sender<AssetLoadFailed>,
+    receiver: Receiver<AssetLoadFailed>,
+}
+
+impl Default for LoadFailedChannel {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        LoadFailedChannel { sender, receiver }
+    }
 }
 
 #[derive(Default)]
@@ -36,12 +85,20 @@ pub(crate) struct AssetRefCounter {
 }
 
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: Arc<dyn AssetIo>,
+    pub(crate) labeled_asset_io: RwLock<HashMap<String, Arc<dyn AssetIo>>>,
+    watching_for_changes: AtomicBool,
     pub(crate) asset_ref_counter: AssetRefCounter,
+    load_failed_channel: LoadFailedChannel,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    processors: RwLock<Vec<Arc<Box<dyn AssetProcessor>>>>,
+    extension_to_processor_index: RwLock<HashMap<String, usize>>,
+    processed_asset_io: RwLock<Option<Arc<dyn AssetIo>>>,
+    savers: RwLock<HashMap<Uuid, Vec<Arc<dyn AssetSaverDynamic>>>>,
+    pending_saves: RwLock<HashMap<Uuid, Vec<(HandleUntyped, AssetPath<'static>)>>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
     task_pool: TaskPool,
 }
@@ -65,16 +122,65 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                processors: Default::default(),
+                extension_to_processor_index: Default::default(),
+                processed_asset_io: Default::default(),
+                savers: Default::default(),
+                pending_saves: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
+                load_failed_channel: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
                 task_pool,
-                asset_io: Box::new(source_io),
+                asset_io: Arc::new(source_io),
+                labeled_asset_io: Default::default(),
+                watching_for_changes: AtomicBool::new(false),
             }),
         }
     }
 
+    /// Registers an [AssetIo] for the asset source with the given name, replacing any previous [AssetIo] for that
+    /// source. Assets in the source are loaded with paths like `name://path/to/asset.png`.
+    pub fn add_asset_source<T: AssetIo>(
+        &self,
+        name: &str,
+        asset_io: T,
+    ) -> Result<(), AssetServerError> {
+        if self.server.watching_for_changes.load(Ordering::Relaxed) {
+            asset_io.watch_for_changes()?;
+        }
+        self.server
+            .labeled_asset_io
+            .write()
+            .insert(name.to_string(), Arc::new(asset_io));
+        Ok(())
+    }
+
+    /// Returns the [AssetIo] for the given asset source, or the default [AssetIo] if `source` is `None`
+    pub fn get_asset_io(&self, source: Option<&str>) -> Result<Arc<dyn AssetIo>, AssetServerError> {
+        match source {
+            Some(source) => self
+                .server
+                .labeled_asset_io
+                .read()
+                .get(source)
+                .cloned()
+                .ok_or_else(|| AssetServerError::MissingAssetSource(source.to_string())),
+            None => Ok(self.server.asset_io.clone()),
+        }
+    }
+
+    /// Returns the names of all registered asset sources. This does not include the default source.
+    pub fn asset_sources(&self) -> Vec<String> {
+        self.server
+            .labeled_asset_io
+            .read()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
         self.server.asset_lifecycles.write().insert(
             T::TYPE_UUID,
@@ -98,8 +204,71 @@ impl AssetServer {
         loaders.push(Arc::new(Box::new(loader)));
     }
 
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let mut processors = self.server.processors.write();
+        let processor_index = processors.len();
+        for extension in processor.extensions().iter() {
+            self.server
+                .extension_to_processor_index
+                .write()
+                .insert(extension.to_string(), processor_index);
+        }
+        processors.push(Arc::new(Box::new(processor)));
+    }
+
+    /// Sets the [AssetIo] that processed assets are read from. Assets in the default source are loaded from their
+    /// processed outputs when one exists.
+    pub fn set_processed_asset_io<T: AssetIo>(&self, asset_io: T) {
+        *self.server.processed_asset_io.write() = Some(Arc::new(asset_io));
+    }
+
+    /// Registers an [AssetSaver], which [AssetServer::save] uses for assets of its type saved to paths with one of its
+    /// extensions
+    pub fn add_saver<T>(&self, saver: T)
+    where
+        T: AssetSaver,
+    {
+        self.server
+            .savers
+            .write()
+            .entry(T::Asset::TYPE_UUID)
+            .or_default()
+            .push(Arc::new(saver));
+    }
+
+    fn get_path_asset_saver<T: Asset>(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn AssetSaverDynamic>, AssetServerError> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .ok_or(AssetServerError::MissingAssetSaver(None))?;
+        let savers = self.server.savers.read();
+        path_extensions(path)
+            .find_map(|extension| {
+                savers.get(&T::TYPE_UUID).and_then(|savers| {
+                    savers
+                        .iter()
+                        .find(|saver| saver.extensions().contains(&extension))
+                })
+            })
+            .cloned()
+            .ok_or_else(|| AssetServerError::MissingAssetSaver(Some(extension.to_string())))
+    }
+
+    /// Watches every asset source for changes, so that modified assets are reloaded
     pub fn watch_for_changes(&self) -> Result<(), AssetServerError> {
         self.server.asset_io.watch_for_changes()?;
+        for asset_io in self.server.labeled_asset_io.read().values() {
+            asset_io.watch_for_changes()?;
+        }
+        self.server
+            .watching_for_changes
+            .store(true, Ordering::Relaxed);
         Ok(())
     }
 
@@ -129,11 +298,35 @@ impl AssetServer {
         &self,
         path: P,
     ) -> Result<Arc<Box<dyn AssetLoader>>, AssetServerError> {
-        path.as_ref()
+        let extension = path
+            .as_ref()
             .extension()
             .and_then(|e| e.to_str())
-            .ok_or(AssetServerError::MissingAssetLoader(None))
-            .and_then(|extension| self.get_asset_loader(extension))
+            .ok_or(AssetServerError::MissingAssetLoader(None))?;
+        path_extensions(path.as_ref())
+            .find_map(|extension| self.get_asset_loader(extension).ok())
+            .ok_or_else(|| AssetServerError::MissingAssetLoader(Some(extension.to_string())))
+    }
+
+    fn get_path_asset_processor<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Option<Arc<Box<dyn AssetProcessor>>> {
+        let extension_to_processor_index = self.server.extension_to_processor_index.read();
+        path_extensions(path.as_ref())
+            .find_map(|extension| extension_to_processor_index.get(extension))
+            .map(|index| self.server.processors.read()[*index].clone())
+    }
+
+    /// Returns the number of strong handles to the given asset, as of the last time unused assets were freed
+    pub fn get_strong_handle_count<H: Into<HandleId>>(&self, handle: H) -> usize {
+        self.server
+            .asset_ref_counter
+            .ref_counts
+            .read()
+            .get(&handle.into())
+            .cloned()
+            .unwrap_or(0)
     }
 
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
@@ -156,6 +349,106 @@ impl AssetServer {
         }
     }
 
+    /// Returns the load state of the given asset and all of its dependencies, recursively. Unlike
+    /// [AssetServer::get_load_state], an asset is only considered loaded once everything it depends on is loaded too.
+    pub fn get_recursive_dependency_load_state<H: Into<HandleId>>(
+        &self,
+        handle: H,
+    ) -> RecursiveDependencyLoadState {
+        let id = match handle.into() {
+            HandleId::AssetPathId(id) => id,
+            HandleId::Id(_, _) => return RecursiveDependencyLoadState::NotLoaded,
+        };
+
+        let asset_sources = self.server.asset_sources.read();
+        let root = match asset_sources.get(&id.source_path_id()) {
+            Some(root) => root,
+            None => return RecursiveDependencyLoadState::NotLoaded,
+        };
+        if root.load_state == LoadState::NotLoaded {
+            return RecursiveDependencyLoadState::NotLoaded;
+        }
+
+        let mut load_state = RecursiveDependencyLoadState::Loaded;
+        let mut visited = HashSet::default();
+        visited.insert(id.source_path_id());
+        let mut pending = vec![root];
+        while let Some(source_info) = pending.pop() {
+            match source_info.load_state {
+                LoadState::Failed => {
+                    return RecursiveDependencyLoadState::Failed(
+                        source_info.asset_path().to_owned(),
+                    )
+                }
+                // dependencies are only queued once their dependent has loaded, so a dependency that has not started
+                // loading yet is about to
+                LoadState::NotLoaded | LoadState::Loading => {
+                    load_state = RecursiveDependencyLoadState::Loading
+                }
+                LoadState::Loaded => {}
+            }
+
+            for dependency in source_info.dependencies() {
+                let source_path_id = SourcePathId::from(dependency);
+                if !visited.insert(source_path_id) {
+                    continue;
+                }
+                match asset_sources.get(&source_path_id) {
+                    Some(dependency_info) => pending.push(dependency_info),
+                    None => load_state = RecursiveDependencyLoadState::Loading,
+                }
+            }
+        }
+
+        load_state
+    }
+
+    /// Returns the paths of the assets that directly depend on the given asset
+    pub fn get_dependents<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Vec<AssetPath<'static>> {
+        let source_path_id = SourcePathId::from(path.into());
+        self.server
+            .asset_sources
+            .read()
+            .values()
+            .filter(|source_info| {
+                source_info
+                    .dependencies()
+                    .any(|dependency| SourcePathId::from(dependency) == source_path_id)
+            })
+            .map(|source_info| source_info.asset_path().to_owned())
+            .collect()
+    }
+
+    /// Reloads every asset loaded from the given path, whatever loader settings it was loaded with, and every asset that
+    /// depends on them, recursively
+    pub(crate) fn reload_with_dependents(&self, source: Option<&str>, path: &Path) {
+        let mut pending = self
+            .server
+            .asset_sources
+            .read()
+            .values()
+            .filter(|source_info| {
+                source_info.source.as_deref() == source && source_info.path == path
+            })
+            .map(|source_info| source_info.asset_path().to_owned())
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            pending.push(
+                AssetPath::new_ref(path, None)
+                    .with_optional_source(source)
+                    .to_owned(),
+            );
+        }
+        let mut visited = HashSet::default();
+        while let Some(asset_path) = pending.pop() {
+            if !visited.insert(SourcePathId::from(&asset_path)) {
+                continue;
+            }
+            pending.extend(self.get_dependents(asset_path.clone()));
+            let _ = self.load_untracked(asset_path, true);
+        }
+    }
+
     pub fn get_group_load_state(&self, handles: impl IntoIterator<Item = HandleId>) -> LoadState {
         let mut load_state = LoadState::Loaded;
         for handle_id in handles {
@@ -179,7 +472,18 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
-    // TODO: properly set failed LoadState in all failure cases
+    /// Loads the asset at the given path with the given loader settings, which take precedence over the settings in
+    /// the asset's meta file. Loading the same path with different settings produces distinct assets.
+    pub fn load_with_settings<'a, T: Asset, P: Into<AssetPath<'a>>, S: Serialize>(
+        &self,
+        path: P,
+        settings: &S,
+    ) -> Handle<T> {
+        let settings =
+            ron::ser::to_string(settings).expect("Loader settings should serialize to RON");
+        self.load(path.into().with_loader_settings(settings))
+    }
+
     async fn load_async<'a, P: Into<AssetPath<'a>>>(
         &self,
         path: P,
@@ -187,21 +491,15 @@ impl AssetServer {
     ) -> Result<AssetPathId, AssetServerError> {
         let asset_path: AssetPath = path.into();
         let asset_loader = self.get_path_asset_loader(asset_path.path())?;
+        let asset_io = self.get_asset_io(asset_path.source())?;
         let asset_path_id: AssetPathId = asset_path.get_id();
 
         // load metadata and update source info. this is done in a scope to ensure we release the locks before loading
-        let version = {
+        let (version, bytes_read) = {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = match asset_sources.entry(asset_path_id.source_path_id()) {
                 Entry::Occupied(entry) => entry.into_mut(),
-                Entry::Vacant(entry) => entry.insert(SourceInfo {
-                    asset_types: Default::default(),
-                    committed_assets: Default::default(),
-                    load_state: LoadState::NotLoaded,
-                    meta: None,
-                    path: asset_path.path().to_owned(),
-                    version: 0,
-                }),
+                Entry::Vacant(entry) => entry.insert(SourceInfo::new(&asset_path)),
             };
 
             // if asset is already loaded (or is loading), don't load again
@@ -214,24 +512,75 @@ impl AssetServer {
             }
 
             source_info.load_state = LoadState::Loading;
+            source_info.load_error = None;
             source_info.committed_assets.clear();
             source_info.version += 1;
             source_info.meta = None;
-            source_info.version
+            source_info.bytes_read = Default::default();
+            (source_info.version, source_info.bytes_read.clone())
         };
 
-        // load the asset bytes
-        let bytes = self.server.asset_io.load_path(asset_path.path()).await?;
+        // settings in the asset path take precedence over the settings in the asset's meta file
+        let meta_path = get_meta_path(asset_path.path());
+        let meta_settings =
+            match asset_path.loader_settings() {
+                Some(_) => None,
+                None => match asset_io.load_path(&meta_path).await {
+                    Ok(bytes) => Some(String::from_utf8(bytes).map_err(|err| {
+                        AssetServerError::AssetLoaderError(anyhow::Error::new(err))
+                    })?),
+                    Err(_) => None,
+                },
+            };
+        let settings_ron = asset_path.loader_settings().or(meta_settings.as_deref());
+
+        // load the asset bytes, preferring a processed output of the asset if there is one. processed outputs are
+        // produced with default settings, so they are skipped if the asset path or its meta file has settings
+        let processed = match settings_ron {
+            Some(_) => None,
+            None => self.load_processed(&asset_path, &*asset_io).await,
+        };
+        let (asset_loader, reader, processed) = match processed {
+            Some((processed, bytes)) => (
+                self.get_path_asset_loader(&processed.output_path)?,
+                Box::new(Cursor::new(bytes)) as Box<dyn AssetReader>,
+                Some(processed),
+            ),
+            None => (
+                asset_loader,
+                asset_io.read_path(asset_path.path()).await?,
+                None,
+            ),
+        };
+
+        let mut reader = CountingReader { reader, bytes_read };
+
+        let settings = match settings_ron {
+            Some(ron) => Some((
+                ron,
+                asset_loader.deserialize_settings(ron).map_err(|error| {
+                    AssetServerError::InvalidLoaderSettings {
+                        path: asset_path.path().to_owned(),
+                        error,
+                    }
+                })?,
+            )),
+            None => None,
+        };
 
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
-            asset_path.path(),
+            &asset_path,
+            settings.as_ref().map(|(ron, value)| LoadContextSettings {
+                ron,
+                value: &**value,
+            }),
             &self.server.asset_ref_counter.channel,
-            &*self.server.asset_io,
+            &*asset_io,
             version,
         );
         asset_loader
-            .load(&bytes, &mut load_context)
+            .load_from_reader(&mut reader, &mut load_context)
             .await
             .map_err(AssetServerError::AssetLoaderError)?;
 
@@ -256,6 +605,7 @@ impl AssetServer {
 
         source_info.meta = Some(SourceMeta {
             assets: load_context.get_asset_metas(),
+            processed,
         });
 
         // load asset dependencies and prepare asset type hashmap
@@ -268,14 +618,179 @@ impl AssetServer {
             }
         }
 
-        self.server
-            .asset_io
-            .watch_path_for_changes(asset_path.path())
-            .unwrap();
+        asset_io.watch_path_for_changes(asset_path.path()).unwrap();
+        if meta_settings.is_some() {
+            asset_io.watch_path_for_changes(&meta_path).unwrap();
+        }
         self.create_assets_in_load_context(&mut load_context);
         Ok(asset_path_id)
     }
 
+    /// Reads the processed output of an asset in the default source, if one exists. Outputs are always checked against
+    /// the hash of their source asset when the source can be read, and are ignored if they are out of date. Hashing reads
+    /// the whole source, so it is skipped when the source's modification time matches the one recorded when it was
+    /// processed.
+    pub(crate) async fn load_processed(
+        &self,
+        asset_path: &AssetPath<'_>,
+        asset_io: &dyn AssetIo,
+    ) -> Option<(ProcessedMeta, Vec<u8>)> {
+        if asset_path.source().is_some() {
+            return None;
+        }
+        let processed_asset_io = self.server.processed_asset_io.read().clone()?;
+        let meta_path = ProcessedMeta::meta_path(asset_path.path());
+        let meta_bytes = processed_asset_io.load_path(&meta_path).await.ok()?;
+        let processed = match ron::de::from_bytes::<ProcessedMeta>(&meta_bytes) {
+            Ok(processed) => processed,
+            Err(err) => {
+                log::warn!(
+                    "Failed to read processed asset meta {:?}: {}",
+                    meta_path,
+                    err
+                );
+                return None;
+            }
+        };
+
+        // the source may have changed since it was processed, even while the app wasn't running. sources that can't be
+        // read, such as in builds that only ship processed assets, always use the processed output
+        let unmodified = processed.source_modified.is_some()
+            && asset_io.modified(asset_path.path()) == processed.source_modified;
+        if let Some(processor) = self
+            .get_path_asset_processor(asset_path.path())
+            .filter(|_| !unmodified)
+        {
+            if let Ok(source_bytes) = asset_io.load_path(asset_path.path()).await {
+                if processed_hash(&source_bytes, &**processor) != processed.hash {
+                    return None;
+                }
+            }
+        }
+
+        match processed_asset_io.load_path(&processed.output_path).await {
+            Ok(bytes) => Some((processed, bytes)),
+            Err(err) => {
+                log::warn!(
+                    "Failed to read processed asset {:?}: {}",
+                    processed.output_path,
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    /// Runs the registered [AssetProcessor]s over every asset in the default source and writes the processed outputs to
+    /// `output_folder`. Assets whose source bytes and processor settings have not changed since they were last
+    /// processed are skipped. Returns the paths of the outputs that were written, relative to `output_folder`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn process_assets<P: AsRef<Path>>(
+        &self,
+        output_folder: P,
+    ) -> Result<Vec<PathBuf>, AssetServerError> {
+        let output_folder = crate::FileAssetIo::get_root_path().join(output_folder.as_ref());
+        let asset_io = self.server.asset_io.clone();
+        let mut source_paths = Vec::new();
+        self.collect_processable_paths(&*asset_io, Path::new(""), &mut source_paths)?;
+
+        let output_folder = &output_folder;
+        let asset_io = &*asset_io;
+        let results = self.server.task_pool.scope(|scope| {
+            for source_path in source_paths.iter() {
+                scope.spawn(async move {
+                    self.process_asset(asset_io, source_path, output_folder)
+                        .await
+                });
+            }
+        });
+
+        results
+            .into_iter()
+            .filter_map(|result| result.transpose())
+            .collect()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn collect_processable_paths(
+        &self,
+        asset_io: &dyn AssetIo,
+        path: &Path,
+        source_paths: &mut Vec<PathBuf>,
+    ) -> Result<(), AssetServerError> {
+        for child_path in asset_io.read_directory(path)? {
+            if asset_io.is_directory(&child_path) {
+                self.collect_processable_paths(asset_io, &child_path, source_paths)?;
+            } else if self.get_path_asset_processor(&child_path).is_some() {
+                source_paths.push(child_path);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn process_asset(
+        &self,
+        asset_io: &dyn AssetIo,
+        source_path: &Path,
+        output_folder: &Path,
+    ) -> Result<Option<PathBuf>, AssetServerError> {
+        use std::fs;
+
+        let processor = match self.get_path_asset_processor(source_path) {
+            Some(processor) => processor,
+            None => return Ok(None),
+        };
+        let source_modified = asset_io.modified(source_path);
+        let bytes = asset_io.load_path(source_path).await?;
+        let hash = processed_hash(&bytes, &**processor);
+
+        // skip assets that are already up to date
+        let meta_path = output_folder.join(ProcessedMeta::meta_path(source_path));
+        if let Some(existing) = fs::read(&meta_path)
+            .ok()
+            .and_then(|meta_bytes| ron::de::from_bytes::<ProcessedMeta>(&meta_bytes).ok())
+        {
+            let existing_output_path = output_folder.join(&existing.output_path);
+            if existing.hash == hash && existing_output_path.exists() {
+                return Ok(None);
+            }
+            let _ = fs::remove_file(existing_output_path);
+        }
+
+        let output = processor
+            .process(&bytes, source_path)
+            .await
+            .map_err(AssetServerError::AssetProcessorError)?;
+        let processed = ProcessedMeta {
+            hash,
+            processor: processor.name().to_string(),
+            output_path: ProcessedMeta::output_path(
+                source_path,
+                hash,
+                processor.output_extension(),
+            ),
+            source_modified,
+        };
+        let meta = ron::ser::to_string_pretty(&processed, Default::default())
+            .map_err(|err| AssetServerError::AssetProcessorError(err.into()))?;
+
+        if let Some(parent) = meta_path.parent() {
+            fs::create_dir_all(parent).map_err(AssetIoError::from)?;
+        }
+        fs::write(output_folder.join(&processed.output_path), output)
+            .map_err(AssetIoError::from)?;
+        fs::write(&meta_path, meta).map_err(AssetIoError::from)?;
+
+        log::debug!(
+            "processed asset {:?} into {:?}",
+            source_path,
+            processed.output_path
+        );
+        Ok(Some(processed.output_path))
+    }
+
     pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> HandleUntyped {
         let handle_id = self.load_untracked(path, false);
         self.get_handle_untyped(handle_id)
@@ -292,34 +807,151 @@ impl AssetServer {
         self.server
             .task_pool
             .spawn(async move {
-                server.load_async(owned_path, force).await.unwrap();
+                if let Err(err) = server.load_async(owned_path.clone(), force).await {
+                    server.set_load_failed(owned_path, err);
+                }
             })
             .detach();
         asset_path.into()
     }
 
-    pub fn load_folder<P: AsRef<Path>>(
+    /// Saves the given asset to the given path, using the [AssetSaver] registered for the asset's type and the path's
+    /// extension. The asset is serialized during the next update, and written to its asset source on the task pool.
+    pub fn save<'a, T: Asset, P: Into<AssetPath<'a>>>(
+        &self,
+        handle: &Handle<T>,
+        path: P,
+    ) -> Result<(), AssetServerError> {
+        let asset_path: AssetPath = path.into();
+        self.get_path_asset_saver::<T>(asset_path.path())?;
+        let asset_io = self.get_asset_io(asset_path.source())?;
+        if asset_io.as_writer().is_none() {
+            return Err(AssetIoError::ReadOnly(asset_path.path().to_owned()).into());
+        }
+
+        self.server
+            .pending_saves
+            .write()
+            .entry(T::TYPE_UUID)
+            .or_default()
+            .push((self.get_handle_untyped(handle), asset_path.to_owned()));
+        Ok(())
+    }
+
+    pub(crate) fn save_assets<T: Asset>(&self, assets: &Assets<T>) {
+        let pending_saves = match self.server.pending_saves.write().remove(&T::TYPE_UUID) {
+            Some(pending_saves) => pending_saves,
+            None => return,
+        };
+
+        // the pending saves hold strong handles, so the assets are not freed before they are saved
+        for (handle, asset_path) in pending_saves {
+            if let Err(err) = self.save_asset(assets, handle.id, &asset_path) {
+                log::error!("Failed to save asset {:?}: {:?}", asset_path, err);
+            }
+        }
+    }
+
+    fn save_asset<T: Asset>(
+        &self,
+        assets: &Assets<T>,
+        handle_id: HandleId,
+        asset_path: &AssetPath<'static>,
+    ) -> Result<(), AssetServerError> {
+        let asset = assets
+            .get(handle_id)
+            .ok_or(AssetServerError::MissingAsset(handle_id))?;
+        let saver = self.get_path_asset_saver::<T>(asset_path.path())?;
+        let save = saver
+            .save_dynamic(asset, asset_path.path())
+            .map_err(AssetServerError::AssetSaverError)?;
+        let asset_io = self.get_asset_io(asset_path.source())?;
+        let asset_path = asset_path.clone();
+        self.server
+            .task_pool
+            .spawn(async move {
+                let bytes = match save() {
+                    Ok(bytes) => bytes,
+                    Err(err) => {
+                        log::error!("Failed to save asset {:?}: {:?}", asset_path, err);
+                        return;
+                    }
+                };
+                let writer = asset_io
+                    .as_writer()
+                    .expect("Asset sources are checked to be writable before saving");
+                match writer.save_path(asset_path.path(), &bytes).await {
+                    Ok(()) => log::debug!("Saved asset {:?}", asset_path),
+                    Err(err) => log::error!("Failed to save asset {:?}: {:?}", asset_path, err),
+                }
+            })
+            .detach();
+        Ok(())
+    }
+
+    fn set_load_failed(&self, asset_path: AssetPath<'static>, error: AssetServerError) {
+        log::error!("Failed to load asset {:?}: {:?}", asset_path, error);
+        let error = Arc::new(error);
+        {
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .entry(asset_path.get_id().source_path_id())
+                .or_insert_with(|| SourceInfo::new(&asset_path));
+            source_info.load_state = LoadState::Failed;
+            source_info.load_error = Some(error.clone());
+        }
+
+        let handle = HandleUntyped::weak(asset_path.get_id().into());
+        self.server
+            .load_failed_channel
+            .sender
+            .send(AssetLoadFailed {
+                path: asset_path,
+                handle,
+                error,
+            })
+            .unwrap();
+    }
+
+    /// Returns the error that made the given asset fail to load, if its last load failed
+    pub fn get_load_error<H: Into<HandleId>>(&self, handle: H) -> Option<Arc<AssetServerError>> {
+        match handle.into() {
+            HandleId::AssetPathId(id) => self
+                .server
+                .asset_sources
+                .read()
+                .get(&id.source_path_id())
+                .and_then(|source_info| source_info.load_error.clone()),
+            HandleId::Id(_, _) => None,
+        }
+    }
+
+    /// Loads every asset in the given folder and its subfolders. The folder path may name an asset source, as in
+    /// `mods://textures`.
+    pub fn load_folder<'a, P: Into<AssetPath<'a>>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
-        if !self.server.asset_io.is_directory(path) {
+        let asset_path: AssetPath = path.into();
+        let asset_io = self.get_asset_io(asset_path.source())?;
+        let path = asset_path.path();
+        if !asset_io.is_directory(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.server.asset_io.read_directory(path.as_ref())? {
-            if self.server.asset_io.is_directory(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+        for child_path in asset_io.read_directory(path)? {
+            let child_asset_path =
+                AssetPath::new_ref(&child_path, None).with_optional_source(asset_path.source());
+            if asset_io.is_directory(&child_path) {
+                handles.extend(self.load_folder(child_asset_path)?);
             } else {
                 if self.get_path_asset_loader(&child_path).is_err() {
                     continue;
                 }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string"));
-                handles.push(handle);
+                handles.push(self.load_untyped(child_asset_path));
             }
         }
 
@@ -381,7 +1013,9 @@ impl AssetServer {
                 .expect("Asset should exist at this point");
             if let Some(asset_lifecycle) = asset_lifecycles.get(&asset_value.type_uuid()) {
                 let asset_path =
-                    AssetPath::new_ref(&load_context.path, label.as_ref().map(|l| l.as_str()));
+                    AssetPath::new_ref(&load_context.path, label.as_ref().map(|l| l.as_str()))
+                        .with_optional_source(load_context.source)
+                        .with_optional_loader_settings(load_context.loader_settings);
                 asset_lifecycle.create_asset(asset_path.into(), asset_value, load_context.version);
             } else {
                 panic!("Failed to find AssetLifecycle for label {:?}, which has an asset type {:?}. Are you sure that is a registered asset type?", label, asset_value.type_uuid());
@@ -432,8 +1066,369 @@ impl AssetServer {
             }
         }
     }
+
+    /// Records the source at `path` as if it had been loaded with the given state and dependencies
+    #[cfg(test)]
+    pub(crate) fn insert_test_source(
+        &self,
+        path: &str,
+        load_state: LoadState,
+        dependencies: &[&str],
+    ) {
+        let asset_path = AssetPath::from(path);
+        let mut source_info = SourceInfo::new(&asset_path);
+        source_info.load_state = load_state;
+        source_info.meta = Some(SourceMeta {
+            assets: vec![crate::AssetMeta {
+                label: None,
+                dependencies: dependencies
+                    .iter()
+                    .map(|dependency| AssetPath::from(*dependency).to_owned())
+                    .collect(),
+                type_uuid: Uuid::nil(),
+            }],
+            processed: None,
+        });
+        self.server
+            .asset_sources
+            .write()
+            .insert(SourcePathId::from(&asset_path), source_info);
+    }
+}
+
+/// Identifies the processed output of the given source bytes. Changes to the processor's name or settings produce a new
+/// hash, so outputs of an older processor are never reused.
+fn processed_hash(bytes: &[u8], processor: &dyn AssetProcessor) -> u64 {
+    let mut hasher = get_hasher();
+    bytes.hash(&mut hasher);
+    processor.name().hash(&mut hasher);
+    processor.settings().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Returns the extensions of the file at `path` from the longest to the shortest, so that loaders of compound
+/// extensions like `scn.json` are preferred over loaders of `json`
+fn path_extensions(path: &Path) -> impl Iterator<Item = &str> {
+    let file_name = path
+        .file_name()
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or("");
+    file_name
+        .match_indices('.')
+        .filter(|(index, _)| *index > 0)
+        .map(move |(index, _)| &file_name[index + 1..])
+        .filter(|extension| !extension.is_empty())
 }
 
 pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     asset_server.free_unused_assets();
 }
+
+/// Serializes the assets of type `T` that were passed to [AssetServer::save] and writes them to their asset sources
+pub fn asset_save_system<T: Asset>(asset_server: Res<AssetServer>, assets: Res<Assets<T>>) {
+    asset_server.save_assets(&assets);
+}
+
+/// Sends an [AssetLoadFailed] event for every asset that failed to load since the last update
+pub fn asset_load_failed_system(
+    asset_server: Res<AssetServer>,
+    mut events: ResMut<Events<AssetLoadFailed>>,
+) {
+    for event in asset_server.server.load_failed_channel.receiver.try_iter() {
+        events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::path_extensions;
+    use crate::{
+        deserialize_settings, AssetLoader, AssetPath, AssetSaver, AssetServer, AssetServerError,
+        Assets, Handle, HandleId, LoadContext, LoadState, LoadedAsset, LoaderSettings,
+        MemoryAssetIo, ProcessedMeta, RecursiveDependencyLoadState, SourcePathId,
+    };
+    use bevy_tasks::TaskPool;
+    use bevy_type_registry::TypeUuid;
+    use bevy_utils::BoxedFuture;
+    use futures_lite::future;
+    use serde::{Deserialize, Serialize};
+    use std::{
+        path::Path,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn recursive_dependency_load_state() {
+        let asset_server = AssetServer::new(MemoryAssetIo::new(), TaskPool::new());
+        asset_server.insert_test_source("scene.gltf", LoadState::Loaded, &["material.mat"]);
+        asset_server.insert_test_source("material.mat", LoadState::Loaded, &["texture.png"]);
+        let scene = HandleId::from("scene.gltf");
+
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(scene),
+            RecursiveDependencyLoadState::Loading
+        );
+        asset_server.insert_test_source("texture.png", LoadState::Loaded, &[]);
+        assert_eq!(
+            asset_server.get_recursive_dependency_load_state(scene),
+            RecursiveDependencyLoadState::Loaded
+        );
+        asset_server.insert_test_source("texture.png", LoadState::Failed, &[]);
+        assert!(matches!(
+            asset_server.get_recursive_dependency_load_state(scene),
+            RecursiveDependencyLoadState::Failed(path) if path.path().to_str() == Some("texture.png")
+        ));
+
+        let dependents = asset_server.get_dependents("texture.png");
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(dependents[0].path().to_str(), Some("material.mat"));
+    }
+
+    #[derive(TypeUuid)]
+    #[uuid = "5e1d0f5c-4a1b-4c4f-9f5b-3c1e9d8a7b60"]
+    struct Text(String);
+
+    struct TextSaver;
+
+    impl AssetSaver for TextSaver {
+        type Asset = Text;
+
+        fn save(&self, asset: &Text, _path: &Path) -> anyhow::Result<Vec<u8>> {
+            Ok(asset.0.as_bytes().to_vec())
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+    }
+
+    #[test]
+    fn save_writes_to_asset_source() {
+        let asset_server = AssetServer::new(MemoryAssetIo::new(), TaskPool::new());
+        asset_server.add_saver(TextSaver);
+        let mut assets = asset_server.register_asset_type::<Text>();
+        let handle = assets.add(Text("hello".to_string()));
+
+        assert!(matches!(
+            asset_server.save(&handle, "notes/hello.bin"),
+            Err(AssetServerError::MissingAssetSaver(_))
+        ));
+        asset_server.save(&handle, "notes/hello.txt").unwrap();
+        asset_server.save_assets(&assets);
+
+        let asset_io = asset_server.get_asset_io(None).unwrap();
+        let start = Instant::now();
+        let bytes = loop {
+            match future::block_on(asset_io.load_path(Path::new("notes/hello.txt"))) {
+                Ok(bytes) => break bytes,
+                Err(_) if start.elapsed() < Duration::from_secs(5) => {
+                    std::thread::sleep(Duration::from_millis(1))
+                }
+                Err(err) => panic!("asset was not saved: {:?}", err),
+            }
+        };
+        assert_eq!(bytes, b"hello");
+    }
+
+    #[test]
+    fn failed_loads_keep_their_error() {
+        let asset_server = AssetServer::new(MemoryAssetIo::new(), TaskPool::new());
+        let handle = asset_server.load_untyped("notes/missing.txt");
+        let event = asset_server
+            .server
+            .load_failed_channel
+            .receiver
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(event.path, AssetPath::from("notes/missing.txt"));
+        assert_eq!(event.handle, handle.clone_weak());
+        assert!(matches!(
+            *event.error,
+            AssetServerError::MissingAssetLoader(Some(ref extension)) if extension == "txt"
+        ));
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Failed);
+        assert!(asset_server.get_load_error(&handle).is_some());
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct TextSettings {
+        uppercase: bool,
+    }
+
+    struct TextLoader;
+
+    impl AssetLoader for TextLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            load_context: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, anyhow::Result<()>> {
+            Box::pin(async move {
+                let mut text = String::from_utf8(bytes.to_vec())?;
+                if let Some(TextSettings { uppercase: true }) = load_context.settings() {
+                    text = text.to_uppercase();
+                }
+                load_context.set_default_asset(LoadedAsset::new(Text(text)));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn deserialize_settings(&self, settings: &str) -> anyhow::Result<Box<dyn LoaderSettings>> {
+            deserialize_settings::<TextSettings>(settings)
+        }
+    }
+
+    fn wait_for_load(
+        asset_server: &AssetServer,
+        assets: &mut Assets<Text>,
+        handle: &Handle<Text>,
+    ) -> LoadState {
+        let start = Instant::now();
+        loop {
+            asset_server.update_asset_storage(assets);
+            match asset_server.get_load_state(handle) {
+                LoadState::Loading | LoadState::NotLoaded
+                    if start.elapsed() < Duration::from_secs(5) =>
+                {
+                    std::thread::sleep(Duration::from_millis(1))
+                }
+                load_state => return load_state,
+            }
+        }
+    }
+
+    #[test]
+    fn meta_files_configure_loaders() {
+        let asset_io = MemoryAssetIo::new()
+            .with_asset("notes/a.txt", "hello")
+            .with_asset("notes/a.txt.meta", "(uppercase: true)")
+            .with_asset("notes/b.txt", "hello")
+            .with_asset("notes/b.txt.meta", "(flip_normals: true)");
+        let asset_server = AssetServer::new(asset_io, TaskPool::new());
+        asset_server.add_loader(TextLoader);
+        let mut assets = asset_server.register_asset_type::<Text>();
+
+        let handle: Handle<Text> = asset_server.load("notes/a.txt");
+        let with_settings: Handle<Text> =
+            asset_server.load_with_settings("notes/a.txt", &TextSettings { uppercase: false });
+        assert_eq!(
+            wait_for_load(&asset_server, &mut assets, &handle),
+            LoadState::Loaded
+        );
+        assert_eq!(
+            wait_for_load(&asset_server, &mut assets, &with_settings),
+            LoadState::Loaded
+        );
+        assert_eq!(assets.get(&handle).unwrap().0, "HELLO");
+        assert_eq!(assets.get(&with_settings).unwrap().0, "hello");
+
+        // settings from a meta file do not change the id of the asset, while settings in its path do
+        assert_eq!(handle.id, HandleId::from("notes/a.txt"));
+        assert_ne!(with_settings.id, handle.id);
+        assert!(asset_server
+            .server
+            .asset_sources
+            .read()
+            .contains_key(&SourcePathId::from(Path::new("notes/a.txt"))));
+
+        // settings meant for another loader fail the load instead of falling back to the defaults
+        let mismatched: Handle<Text> = asset_server.load("notes/b.txt");
+        assert_eq!(
+            wait_for_load(&asset_server, &mut assets, &mismatched),
+            LoadState::Failed
+        );
+        assert!(matches!(
+            asset_server.get_load_error(&mismatched).as_deref(),
+            Some(AssetServerError::InvalidLoaderSettings { .. })
+        ));
+    }
+
+    #[test]
+    fn meta_files_take_precedence_over_processed_outputs() {
+        let asset_server = AssetServer::new(
+            MemoryAssetIo::new()
+                .with_asset("notes/a.txt", "hello")
+                .with_asset("notes/a.txt.meta", "(uppercase: true)"),
+            TaskPool::new(),
+        );
+        let processed = ProcessedMeta {
+            hash: 0,
+            processor: "UppercaseProcessor".to_string(),
+            output_path: ProcessedMeta::output_path(Path::new("notes/a.txt"), 0, "upper"),
+            source_modified: None,
+        };
+        asset_server.set_processed_asset_io(
+            MemoryAssetIo::new()
+                .with_asset(&processed.output_path, "processed")
+                .with_asset(
+                    ProcessedMeta::meta_path(Path::new("notes/a.txt")),
+                    ron::ser::to_string(&processed).unwrap(),
+                ),
+        );
+        asset_server.add_loader(TextLoader);
+        let mut assets = asset_server.register_asset_type::<Text>();
+
+        // the processed output was produced with default settings, so the source is loaded with the meta settings
+        let handle: Handle<Text> = asset_server.load("notes/a.txt");
+        assert_eq!(
+            wait_for_load(&asset_server, &mut assets, &handle),
+            LoadState::Loaded
+        );
+        assert_eq!(assets.get(&handle).unwrap().0, "HELLO");
+    }
+
+    #[test]
+    fn reloads_keep_loader_settings() {
+        let asset_server = AssetServer::new(
+            MemoryAssetIo::new()
+                .with_asset("notes/a.txt", "hello")
+                .with_asset("notes/a.txt.meta", "(uppercase: true)"),
+            TaskPool::new(),
+        );
+        asset_server.add_loader(TextLoader);
+        let mut assets = asset_server.register_asset_type::<Text>();
+
+        let handle: Handle<Text> = asset_server.load("notes/a.txt");
+        let with_settings: Handle<Text> =
+            asset_server.load_with_settings("notes/a.txt", &TextSettings { uppercase: false });
+        wait_for_load(&asset_server, &mut assets, &handle);
+        wait_for_load(&asset_server, &mut assets, &with_settings);
+
+        asset_server
+            .server
+            .asset_io
+            .downcast_ref::<MemoryAssetIo>()
+            .unwrap()
+            .insert("notes/a.txt", "goodbye");
+        asset_server.reload_with_dependents(None, Path::new("notes/a.txt"));
+
+        let start = Instant::now();
+        while assets.get(&with_settings).unwrap().0 != "goodbye"
+            || assets.get(&handle).unwrap().0 != "GOODBYE"
+        {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::sleep(Duration::from_millis(1));
+            asset_server.update_asset_storage(&mut assets);
+        }
+    }
+
+    #[test]
+    fn compound_extensions_are_tried_first() {
+        fn extensions(path: &str) -> Vec<&str> {
+            path_extensions(Path::new(path)).collect()
+        }
+        assert_eq!(
+            extensions("levels/forest.scn.json"),
+            vec!["scn.json", "json"]
+        );
+        assert_eq!(extensions("levels.v2/forest.scn"), vec!["scn"]);
+        assert_eq!(extensions(".hidden"), Vec::<&str>::new());
+        assert_eq!(extensions("readme"), Vec::<&str>::new());
+    }
+}
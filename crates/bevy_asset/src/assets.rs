@@ -1,12 +1,14 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetLoader, AssetServer, Handle, HandleId, RefChange,
+    asset_diagnostics_system, asset_save_system, update_asset_storage_system, Asset, AssetByteSize,
+    AssetIo, AssetLoader, AssetProcessor, AssetSaver, AssetServer, EmbeddedAssets, Handle,
+    HandleId, RefChange,
 };
 use bevy_app::{prelude::Events, AppBuilder};
 use bevy_ecs::{FromResources, IntoQuerySystem, ResMut};
 use bevy_type_registry::RegisterType;
 use bevy_utils::HashMap;
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 /// Events that happen on assets of type `T`
 pub enum AssetEvent<T: Asset> {
@@ -49,6 +51,7 @@ pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
     pub(crate) ref_change_sender: Sender<RefChange>,
+    byte_size: Option<fn(&T) -> usize>,
 }
 
 impl<T: Asset> Assets<T> {
@@ -57,9 +60,25 @@ impl<T: Asset> Assets<T> {
             assets: HashMap::default(),
             events: Events::default(),
             ref_change_sender,
+            byte_size: None,
         }
     }
 
+    /// Lets [Assets::get_byte_size] report the size of assets of this type
+    pub fn register_byte_size(&mut self)
+    where
+        T: AssetByteSize,
+    {
+        self.byte_size = Some(T::byte_size);
+    }
+
+    /// Returns the size of the given asset in bytes, if its type's size was registered with
+    /// [Assets::register_byte_size]
+    pub fn get_byte_size<H: Into<HandleId>>(&self, handle: H) -> Option<usize> {
+        let byte_size = self.byte_size?;
+        self.get(handle).map(byte_size)
+    }
+
     pub fn add(&mut self, asset: T) -> Handle<T> {
         let id = HandleId::random::<T>();
         self.assets.insert(id, asset);
@@ -199,12 +218,28 @@ pub trait AddAsset {
     fn add_asset<T>(&mut self) -> &mut Self
     where
         T: Asset;
+    fn register_asset_byte_size<T>(&mut self) -> &mut Self
+    where
+        T: Asset + AssetByteSize;
     fn init_asset_loader<T>(&mut self) -> &mut Self
     where
         T: AssetLoader + FromResources;
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromResources;
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver;
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
+    fn add_asset_source<T>(&mut self, name: &str, asset_io: T) -> &mut Self
+    where
+        T: AssetIo;
+    fn add_embedded_asset<P: AsRef<Path>>(&mut self, path: P, bytes: &'static [u8]) -> &mut Self;
 }
 
 impl AddAsset for AppBuilder {
@@ -227,9 +262,25 @@ impl AddAsset for AppBuilder {
                 crate::stage::LOAD_ASSETS,
                 update_asset_storage_system::<T>.system(),
             )
+            .add_system_to_stage(super::stage::ASSET_EVENTS, asset_save_system::<T>.system())
+            .add_system_to_stage(
+                super::stage::ASSET_EVENTS,
+                asset_diagnostics_system::<T>.system(),
+            )
             .add_event::<AssetEvent<T>>()
     }
 
+    fn register_asset_byte_size<T>(&mut self) -> &mut Self
+    where
+        T: Asset + AssetByteSize,
+    {
+        self.resources()
+            .get_mut::<Assets<T>>()
+            .expect("Assets do not exist. Consider adding them with add_asset.")
+            .register_byte_size();
+        self
+    }
+
     fn init_asset_loader<T>(&mut self) -> &mut Self
     where
         T: AssetLoader + FromResources,
@@ -247,4 +298,53 @@ impl AddAsset for AppBuilder {
             .add_loader(loader);
         self
     }
+
+    fn init_asset_saver<T>(&mut self) -> &mut Self
+    where
+        T: AssetSaver + FromResources,
+    {
+        self.add_asset_saver(T::from_resources(self.resources()))
+    }
+
+    fn add_asset_saver<T>(&mut self, saver: T) -> &mut Self
+    where
+        T: AssetSaver,
+    {
+        self.resources()
+            .get::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_saver(saver);
+        self
+    }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.resources()
+            .get::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_processor(processor);
+        self
+    }
+
+    fn add_asset_source<T>(&mut self, name: &str, asset_io: T) -> &mut Self
+    where
+        T: AssetIo,
+    {
+        self.resources()
+            .get::<AssetServer>()
+            .expect("AssetServer does not exist. Consider adding it as a resource.")
+            .add_asset_source(name, asset_io)
+            .unwrap_or_else(|err| panic!("Failed to add asset source {}: {}", name, err));
+        self
+    }
+
+    fn add_embedded_asset<P: AsRef<Path>>(&mut self, path: P, bytes: &'static [u8]) -> &mut Self {
+        self.resources()
+            .get::<EmbeddedAssets>()
+            .expect("EmbeddedAssets does not exist. Consider adding the AssetPlugin.")
+            .insert(path, bytes);
+        self
+    }
 }
@@ -0,0 +1,238 @@
+use crate::{Asset, AssetEvent, AssetPath, AssetServer, Assets, HandleId};
+use bevy_app::prelude::*;
+use bevy_ecs::{IntoQuerySystem, Local, Res, ResMut};
+use bevy_utils::HashMap;
+
+/// Implemented by assets that can report how much memory they use, so that [AssetDiagnostics] can show it. Register
+/// it with [AddAsset::register_asset_byte_size](crate::AddAsset::register_asset_byte_size).
+pub trait AssetByteSize {
+    fn byte_size(&self) -> usize;
+}
+
+/// Information about an asset that is currently loaded
+#[derive(Debug, Clone)]
+pub struct LoadedAssetInfo {
+    pub id: HandleId,
+    /// The path the asset was loaded from, or `None` if it was added at runtime
+    pub path: Option<AssetPath<'static>>,
+    pub type_name: &'static str,
+    pub strong_handle_count: usize,
+    /// The size of the asset in bytes, if its type implements [AssetByteSize]
+    pub byte_size: Option<usize>,
+    /// The last frame in which the asset was created, modified, or had its strong handle count change
+    pub last_touched_frame: u64,
+}
+
+/// Lists every loaded asset of every type. This is only kept up to date while diagnostics are enabled, usually by
+/// [AssetDiagnosticsPlugin].
+#[derive(Debug, Default)]
+pub struct AssetDiagnostics {
+    pub enabled: bool,
+    frame: u64,
+    assets: HashMap<HandleId, LoadedAssetInfo>,
+}
+
+impl AssetDiagnostics {
+    /// The number of frames counted since diagnostics were enabled
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn get(&self, id: HandleId) -> Option<&LoadedAssetInfo> {
+        self.assets.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LoadedAssetInfo> {
+        self.assets.values()
+    }
+
+    /// Returns the assets that have been loaded but untouched for at least the given number of frames. Assets that
+    /// stay alive without being used are often kept alive by a forgotten strong [Handle](crate::Handle).
+    pub fn untouched_for(&self, frames: u64) -> impl Iterator<Item = &LoadedAssetInfo> {
+        let frame = self.frame;
+        self.assets
+            .values()
+            .filter(move |info| frame.saturating_sub(info.last_touched_frame) >= frames)
+    }
+
+    /// The total size of all assets that report their size
+    pub fn total_byte_size(&self) -> usize {
+        self.assets.values().filter_map(|info| info.byte_size).sum()
+    }
+
+    fn update<T: Asset>(
+        &mut self,
+        asset_server: &AssetServer,
+        assets: &Assets<T>,
+        events: &Events<AssetEvent<T>>,
+        event_reader: &mut EventReader<AssetEvent<T>>,
+    ) {
+        let frame = self.frame;
+        for event in event_reader.iter(events) {
+            match event {
+                AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                    if let Some(info) = self.assets.get_mut(&handle.id) {
+                        info.last_touched_frame = frame;
+                    }
+                }
+                AssetEvent::Removed { handle } => {
+                    self.assets.remove(&handle.id);
+                }
+            }
+        }
+
+        for id in assets.ids() {
+            let strong_handle_count = asset_server.get_strong_handle_count(id);
+            let byte_size = assets.get_byte_size(id);
+            let info = self.assets.entry(id).or_insert_with(|| LoadedAssetInfo {
+                id,
+                path: asset_server.get_handle_path(id).map(|path| path.to_owned()),
+                type_name: std::any::type_name::<T>(),
+                strong_handle_count,
+                byte_size,
+                last_touched_frame: frame,
+            });
+            if info.strong_handle_count != strong_handle_count {
+                info.strong_handle_count = strong_handle_count;
+                info.last_touched_frame = frame;
+            }
+            info.byte_size = byte_size;
+        }
+    }
+}
+
+/// Updates [AssetDiagnostics] with the assets of type `T`
+pub fn asset_diagnostics_system<T: Asset>(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<T>>,
+    events: Res<Events<AssetEvent<T>>>,
+    mut event_reader: Local<EventReader<AssetEvent<T>>>,
+    mut diagnostics: ResMut<AssetDiagnostics>,
+) {
+    if diagnostics.enabled {
+        diagnostics.update(&asset_server, &assets, &events, &mut event_reader);
+    }
+}
+
+fn asset_diagnostics_frame_system(mut diagnostics: ResMut<AssetDiagnostics>) {
+    if diagnostics.enabled {
+        diagnostics.frame += 1;
+    }
+}
+
+/// Enables [AssetDiagnostics] and periodically logs the assets that have been untouched for a while
+pub struct AssetDiagnosticsPlugin {
+    /// Assets untouched for at least this many frames are logged
+    pub untouched_frames: u64,
+    /// The number of frames between logs
+    pub log_interval_frames: u64,
+}
+
+impl Default for AssetDiagnosticsPlugin {
+    fn default() -> Self {
+        AssetDiagnosticsPlugin {
+            untouched_frames: 600,
+            log_interval_frames: 600,
+        }
+    }
+}
+
+struct UntouchedAssetLogState {
+    untouched_frames: u64,
+    log_interval_frames: u64,
+}
+
+impl Plugin for AssetDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.resources_mut()
+            .get_or_insert_with(AssetDiagnostics::default)
+            .enabled = true;
+        app.add_resource(UntouchedAssetLogState {
+            untouched_frames: self.untouched_frames,
+            log_interval_frames: self.log_interval_frames,
+        })
+        .add_system_to_stage(
+            crate::stage::LOAD_ASSETS,
+            asset_diagnostics_frame_system.system(),
+        )
+        .add_system_to_stage(bevy_app::stage::LAST, log_untouched_assets_system.system());
+    }
+}
+
+fn log_untouched_assets_system(
+    state: Res<UntouchedAssetLogState>,
+    diagnostics: Res<AssetDiagnostics>,
+) {
+    if state.log_interval_frames == 0 || diagnostics.frame % state.log_interval_frames != 0 {
+        return;
+    }
+
+    for info in diagnostics.untouched_for(state.untouched_frames) {
+        log::info!(
+            "Asset {:?} ({}) untouched for {} frames: path {:?}, {} strong handles, {} bytes",
+            info.id,
+            info.type_name,
+            diagnostics.frame - info.last_touched_frame,
+            info.path,
+            info.strong_handle_count,
+            info.byte_size
+                .map_or_else(|| "unknown".to_string(), |size| size.to_string()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetByteSize, AssetDiagnostics};
+    use crate::{AssetEvent, AssetServer, MemoryAssetIo};
+    use bevy_app::prelude::*;
+    use bevy_tasks::TaskPool;
+    use bevy_type_registry::TypeUuid;
+
+    #[derive(TypeUuid)]
+    #[uuid = "0c6e8a3e-8f0a-4d8e-bb0e-7a34b7a1c2d9"]
+    struct Blob(Vec<u8>);
+
+    impl AssetByteSize for Blob {
+        fn byte_size(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    #[test]
+    fn untouched_assets_are_reported() {
+        let asset_server = AssetServer::new(MemoryAssetIo::new(), TaskPool::new());
+        let mut assets = asset_server.register_asset_type::<Blob>();
+        assets.register_byte_size();
+        let mut events = Events::<AssetEvent<Blob>>::default();
+        let mut event_reader = events.get_reader();
+        let mut diagnostics = AssetDiagnostics {
+            enabled: true,
+            ..Default::default()
+        };
+
+        let handle = assets.add(Blob(vec![0; 16]));
+        asset_server.free_unused_assets();
+        diagnostics.update(&asset_server, &assets, &events, &mut event_reader);
+        let info = diagnostics.get(handle.id).unwrap();
+        assert_eq!(info.strong_handle_count, 1);
+        assert_eq!(info.byte_size, Some(16));
+        assert_eq!(diagnostics.total_byte_size(), 16);
+
+        diagnostics.frame = 10;
+        diagnostics.update(&asset_server, &assets, &events, &mut event_reader);
+        assert_eq!(diagnostics.untouched_for(10).count(), 1);
+
+        let _second_handle = handle.clone();
+        asset_server.free_unused_assets();
+        diagnostics.update(&asset_server, &assets, &events, &mut event_reader);
+        assert_eq!(diagnostics.untouched_for(10).count(), 0);
+
+        assets.remove(&handle);
+        events.send(AssetEvent::Removed {
+            handle: handle.clone_weak(),
+        });
+        diagnostics.update(&asset_server, &assets, &events, &mut event_reader);
+        assert!(diagnostics.get(handle.id).is_none());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{AssetIo, AssetIoError, AssetReader, MemoryAssetIo};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the asset source that only contains embedded assets, as in `embedded://shaders/sprite.vert`
+pub const EMBEDDED_ASSET_SOURCE: &str = "embedded";
+
+/// Asset bytes that were compiled into the binary, usually with the [embedded_asset](crate::embedded_asset) macro.
+///
+/// [AssetPlugin](crate::AssetPlugin) layers embedded assets beneath the default asset source, so
+/// `AssetServer::load("shaders/sprite.vert")` loads the file on disk if it exists and the embedded bytes otherwise.
+/// Embedded assets can also be loaded explicitly from the [EMBEDDED_ASSET_SOURCE] source.
+#[derive(Clone, Default)]
+pub struct EmbeddedAssets {
+    assets: Arc<MemoryAssetIo>,
+}
+
+impl EmbeddedAssets {
+    pub fn insert<P: AsRef<Path>>(&self, path: P, bytes: &'static [u8]) {
+        self.assets.insert_static(path, bytes);
+    }
+
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.assets.contains(path)
+    }
+}
+
+impl AssetIo for EmbeddedAssets {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        self.assets.load_path(path)
+    }
+
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        self.assets.read_path(path)
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.assets.read_directory(path)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.assets.is_directory(path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+/// Compiles the given file into the binary and registers it as an embedded asset at the given asset path.
+/// The file path is relative to the current source file, like [include_bytes].
+///
+/// ```ignore
+/// embedded_asset!(app, "shaders/sprite.vert", "sprite.vert");
+/// // later, loaded just like an asset on disk:
+/// let shader: Handle<Shader> = asset_server.load("shaders/sprite.vert");
+/// ```
+#[macro_export]
+macro_rules! embedded_asset {
+    ($app: expr, $asset_path: expr, $file_path: expr) => {
+        $crate::AddAsset::add_embedded_asset($app, $asset_path, include_bytes!($file_path))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EmbeddedAssets;
+    use crate::{AssetIo, LayeredAssetIo, MemoryAssetIo};
+    use bevy_app::AppBuilder;
+    use futures_lite::future;
+    use std::path::Path;
+
+    #[test]
+    fn embedded_assets_are_overridden_by_earlier_layers() {
+        let embedded = EmbeddedAssets::default();
+        let mut app = AppBuilder::empty();
+        app.add_resource(embedded.clone());
+        embedded_asset!(&mut app, "source/embedded.rs", "embedded.rs");
+        embedded.insert("shaders/sprite.vert", b"embedded shader");
+
+        let asset_io = LayeredAssetIo::new()
+            .with_layer(MemoryAssetIo::new().with_asset("shaders/sprite.vert", "disk shader"))
+            .with_layer(embedded);
+        let load = |path: &str| future::block_on(asset_io.load_path(Path::new(path))).unwrap();
+
+        assert_eq!(load("shaders/sprite.vert"), b"disk shader");
+        assert_eq!(load("source/embedded.rs"), include_bytes!("embedded.rs"));
+    }
+}
@@ -1,12 +1,21 @@
-use crate::{path::AssetPath, LabelId};
+use crate::{path::AssetPath, AssetServerError, LabelId, ProcessedMeta};
 use bevy_utils::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use uuid::Uuid;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SourceMeta {
     pub assets: Vec<AssetMeta>,
+    /// Set if the assets were loaded from a processed output rather than the source asset
+    #[serde(default)]
+    pub processed: Option<ProcessedMeta>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,14 +29,58 @@ pub struct AssetMeta {
 #[derive(Clone, Debug)]
 pub struct SourceInfo {
     pub meta: Option<SourceMeta>,
+    /// The name of the asset source this asset was loaded from, or `None` for the default source
+    pub source: Option<String>,
     pub path: PathBuf,
+    /// The loader settings in the [AssetPath] this asset was loaded with, if any
+    pub loader_settings: Option<String>,
     pub asset_types: HashMap<LabelId, Uuid>,
     pub load_state: LoadState,
+    /// The error that made the last load of this asset fail
+    pub load_error: Option<Arc<AssetServerError>>,
     pub committed_assets: HashSet<LabelId>,
     pub version: usize,
+    pub(crate) bytes_read: Arc<AtomicUsize>,
 }
 
 impl SourceInfo {
+    pub fn new(asset_path: &AssetPath) -> Self {
+        SourceInfo {
+            asset_types: Default::default(),
+            committed_assets: Default::default(),
+            load_state: LoadState::NotLoaded,
+            load_error: None,
+            meta: None,
+            source: asset_path.source().map(|source| source.to_string()),
+            path: asset_path.path().to_owned(),
+            loader_settings: asset_path
+                .loader_settings()
+                .map(|settings| settings.to_string()),
+            version: 0,
+            bytes_read: Default::default(),
+        }
+    }
+
+    /// The number of bytes read from the asset source by the current load of this asset
+    pub fn bytes_read(&self) -> usize {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// The [AssetPath] this asset was loaded with, without a label
+    pub fn asset_path(&self) -> AssetPath<'_> {
+        AssetPath::new_ref(&self.path, None)
+            .with_optional_source(self.source.as_deref())
+            .with_optional_loader_settings(self.loader_settings.as_deref())
+    }
+
+    /// The dependencies of every asset loaded from this source, if it has been loaded
+    pub fn dependencies(&self) -> impl Iterator<Item = &AssetPath<'static>> {
+        self.meta
+            .iter()
+            .flat_map(|meta| meta.assets.iter())
+            .flat_map(|asset_meta| asset_meta.dependencies.iter())
+    }
+
     pub fn is_loaded(&self) -> bool {
         self.meta.as_ref().map_or(false, |meta| {
             self.committed_assets.len() == meta.assets.len()
@@ -47,3 +100,15 @@ pub enum LoadState {
     Loaded,
     Failed,
 }
+
+/// The load state of an asset and all of its dependencies, recursively
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecursiveDependencyLoadState {
+    NotLoaded,
+    /// The asset or at least one of its dependencies is still loading
+    Loading,
+    /// The asset and all of its dependencies are loaded
+    Loaded,
+    /// The asset or one of its dependencies failed to load. This holds the path of the first asset found that failed.
+    Failed(AssetPath<'static>),
+}
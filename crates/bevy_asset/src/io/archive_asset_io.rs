@@ -0,0 +1,249 @@
+use crate::{
+    io::memory_asset_io::{is_directory_in_paths, normalize_path, read_directory_from_paths},
+    AssetIo, AssetIoError, FileAssetIo,
+};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::HashMap;
+use parking_lot::Mutex;
+use std::{
+    fs::File,
+    io::{self, Read, Seek},
+    path::{Path, PathBuf},
+};
+
+/// The source of an archive's bytes, such as a file or an in-memory buffer
+trait ArchiveReader: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ArchiveReader for T {}
+
+enum Archive {
+    #[cfg(feature = "zip")]
+    Zip(Mutex<zip::ZipArchive<Box<dyn ArchiveReader>>>),
+    #[cfg(feature = "tar")]
+    Tar(Mutex<Box<dyn ArchiveReader>>),
+}
+
+/// Where an asset's bytes live inside an archive
+#[derive(Debug, Clone, Copy)]
+enum ArchiveEntry {
+    #[cfg(feature = "zip")]
+    Zip { index: usize },
+    #[cfg(feature = "tar")]
+    Tar { offset: u64, size: u64 },
+}
+
+/// Loads assets from a single zip (`.zip`) or tar (`.tar`) archive. The archive's table of contents is read up front,
+/// and asset bytes are read from the archive on demand.
+pub struct ArchiveAssetIo {
+    archive: Archive,
+    entries: HashMap<PathBuf, ArchiveEntry>,
+}
+
+impl ArchiveAssetIo {
+    /// Opens the archive at the given path. Relative paths are resolved the same way as [FileAssetIo] paths.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, AssetIoError> {
+        let path = FileAssetIo::get_root_path().join(path.as_ref());
+        let file = File::open(&path).map_err(|err| {
+            if err.kind() == io::ErrorKind::NotFound {
+                AssetIoError::NotFound(path.clone())
+            } else {
+                err.into()
+            }
+        })?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "zip")]
+            Some("zip") => Self::open_zip(Box::new(file)),
+            #[cfg(feature = "tar")]
+            Some("tar") => Self::open_tar(Box::new(file)),
+            _ => Err(AssetIoError::UnsupportedArchiveFormat(path)),
+        }
+    }
+
+    /// Reads assets from a zip archive in memory, such as one included in the binary with `include_bytes!`
+    #[cfg(feature = "zip")]
+    pub fn from_zip_bytes(bytes: Vec<u8>) -> Result<Self, AssetIoError> {
+        Self::open_zip(Box::new(io::Cursor::new(bytes)))
+    }
+
+    /// Reads assets from a tar archive in memory, such as one included in the binary with `include_bytes!`
+    #[cfg(feature = "tar")]
+    pub fn from_tar_bytes(bytes: Vec<u8>) -> Result<Self, AssetIoError> {
+        Self::open_tar(Box::new(io::Cursor::new(bytes)))
+    }
+
+    #[cfg(feature = "zip")]
+    fn open_zip(reader: Box<dyn ArchiveReader>) -> Result<Self, AssetIoError> {
+        let mut archive = zip::ZipArchive::new(reader).map_err(zip_error)?;
+        let mut entries = HashMap::default();
+        for index in 0..archive.len() {
+            let entry = archive.by_index(index).map_err(zip_error)?;
+            if entry.is_file() {
+                entries.insert(
+                    normalize_path(Path::new(entry.name())),
+                    ArchiveEntry::Zip { index },
+                );
+            }
+        }
+
+        Ok(Self {
+            archive: Archive::Zip(Mutex::new(archive)),
+            entries,
+        })
+    }
+
+    #[cfg(feature = "tar")]
+    fn open_tar(mut reader: Box<dyn ArchiveReader>) -> Result<Self, AssetIoError> {
+        let mut entries = HashMap::default();
+        for entry in tar::Archive::new(&mut reader).entries()? {
+            let entry = entry?;
+            if entry.header().entry_type().is_file() {
+                entries.insert(
+                    normalize_path(&entry.path()?),
+                    ArchiveEntry::Tar {
+                        offset: entry.raw_file_position(),
+                        size: entry.size(),
+                    },
+                );
+            }
+        }
+
+        Ok(Self {
+            archive: Archive::Tar(Mutex::new(reader)),
+            entries,
+        })
+    }
+
+    fn read_entry(&self, entry: ArchiveEntry) -> Result<Vec<u8>, AssetIoError> {
+        let mut bytes = Vec::new();
+        match (&self.archive, entry) {
+            #[cfg(feature = "zip")]
+            (Archive::Zip(archive), ArchiveEntry::Zip { index }) => {
+                let mut archive = archive.lock();
+                let mut file = archive.by_index(index).map_err(zip_error)?;
+                file.read_to_end(&mut bytes)?;
+            }
+            #[cfg(feature = "tar")]
+            (Archive::Tar(reader), ArchiveEntry::Tar { offset, size }) => {
+                let mut reader = reader.lock();
+                reader.seek(io::SeekFrom::Start(offset))?;
+                bytes.resize(size as usize, 0);
+                reader.read_exact(&mut bytes)?;
+            }
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("archive entries always match their archive format"),
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "zip")]
+fn zip_error(err: zip::result::ZipError) -> AssetIoError {
+    match err {
+        zip::result::ZipError::Io(err) => AssetIoError::Io(err),
+        err => AssetIoError::Io(io::Error::new(io::ErrorKind::InvalidData, err)),
+    }
+}
+
+impl AssetIo for ArchiveAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let entry = self
+                .entries
+                .get(&normalize_path(path))
+                .cloned()
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+            self.read_entry(entry)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        read_directory_from_paths(self.entries.keys(), path)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        is_directory_in_paths(self.entries.keys(), path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, any(feature = "zip", feature = "tar")))]
+mod tests {
+    use super::ArchiveAssetIo;
+    use crate::AssetIo;
+    use futures_lite::future;
+    use std::path::{Path, PathBuf};
+
+    const FILES: &[(&str, &[u8])] = &[
+        ("textures/grass.png", b"grass"),
+        ("textures/ui/button.png", b"button"),
+        ("scenes/level.scn", b"level"),
+    ];
+
+    fn assert_archive_contents(asset_io: &ArchiveAssetIo) {
+        for (path, bytes) in FILES.iter() {
+            let loaded = future::block_on(asset_io.load_path(Path::new(path))).unwrap();
+            assert_eq!(loaded, *bytes);
+        }
+        assert!(future::block_on(asset_io.load_path(Path::new("missing.png"))).is_err());
+
+        assert!(asset_io.is_directory(Path::new("textures")));
+        assert!(!asset_io.is_directory(Path::new("textures/grass.png")));
+        let mut paths = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("textures/grass.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn load_from_zip() {
+        use std::io::Write;
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (path, bytes) in FILES.iter() {
+            writer
+                .start_file(*path, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(bytes).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+
+        assert_archive_contents(&ArchiveAssetIo::from_zip_bytes(bytes).unwrap());
+    }
+
+    #[cfg(feature = "tar")]
+    #[test]
+    fn load_from_tar() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (path, bytes) in FILES.iter() {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(bytes.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, *bytes).unwrap();
+        }
+        let bytes = builder.into_inner().unwrap();
+
+        assert_archive_contents(&ArchiveAssetIo::from_tar_bytes(bytes).unwrap());
+    }
+}
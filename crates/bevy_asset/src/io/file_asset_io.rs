@@ -1,15 +1,20 @@
-use crate::{filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetServer};
+use crate::{
+    filesystem_watcher::FilesystemWatcher, AssetIo, AssetIoError, AssetReader, AssetServer,
+    AssetWriter, LayeredAssetIo, META_EXTENSION,
+};
 use anyhow::Result;
 use bevy_ecs::{bevy_utils::BoxedFuture, Res};
 use bevy_utils::HashSet;
 use crossbeam_channel::TryRecvError;
 use fs::File;
+use futures_lite::io::AssertAsync;
 use io::Read;
 use parking_lot::RwLock;
 use std::{
     env, fs, io,
     path::{Path, PathBuf},
     sync::Arc,
+    time::SystemTime,
 };
 
 pub struct FileAssetIo {
@@ -61,6 +66,25 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    /// Reads the file on demand. Reads block the task they are awaited on, just like [FileAssetIo::load_path].
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            match File::open(self.root_path.join(path)) {
+                Ok(file) => Ok(Box::new(AssertAsync::new(file)) as Box<dyn AssetReader>),
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        Err(AssetIoError::NotFound(path.to_owned()))
+                    } else {
+                        Err(e.into())
+                    }
+                }
+            }
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -101,17 +125,59 @@ impl AssetIo for FileAssetIo {
     fn is_directory(&self, path: &Path) -> bool {
         self.root_path.join(path).is_dir()
     }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        fs::metadata(self.root_path.join(path))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    fn as_writer(&self) -> Option<&dyn AssetWriter> {
+        Some(self)
+    }
+}
+
+impl AssetWriter for FileAssetIo {
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            let path = self.root_path.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, bytes)?;
+            Ok(())
+        })
+    }
 }
 
 #[cfg(all(feature = "filesystem_watcher", not(target_arch = "wasm32")))]
 pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
+    watch_asset_source(&asset_server, None, &*asset_server.server.asset_io);
+    for (source, asset_io) in asset_server.server.labeled_asset_io.read().iter() {
+        watch_asset_source(&asset_server, Some(source), &**asset_io);
+    }
+}
+
+#[cfg(all(feature = "filesystem_watcher", not(target_arch = "wasm32")))]
+fn watch_asset_source(asset_server: &AssetServer, source: Option<&str>, asset_io: &dyn AssetIo) {
+    if let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() {
+        reload_changed_assets(asset_server, source, asset_io);
+    } else if let Some(asset_io) = asset_io.downcast_ref::<LayeredAssetIo>() {
+        for layer in asset_io.layers() {
+            if let Some(layer) = layer.downcast_ref::<FileAssetIo>() {
+                reload_changed_assets(asset_server, source, layer);
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "filesystem_watcher", not(target_arch = "wasm32")))]
+fn reload_changed_assets(asset_server: &AssetServer, source: Option<&str>, asset_io: &FileAssetIo) {
     let mut changed = HashSet::default();
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
-        };
     let watcher = asset_io.filesystem_watcher.read();
     if let Some(ref watcher) = *watcher {
         loop {
@@ -128,8 +194,13 @@ pub fn filesystem_watcher_system(asset_server: Res<AssetServer>) {
             {
                 for path in paths.iter() {
                     if !changed.contains(path) {
-                        let relative_path = path.strip_prefix(&asset_io.root_path).unwrap();
-                        let _ = asset_server.load_untracked(relative_path, true);
+                        let mut relative_path =
+                            path.strip_prefix(&asset_io.root_path).unwrap().to_owned();
+                        // a changed meta file reloads the asset it holds the settings for
+                        if relative_path.extension() == Some(META_EXTENSION.as_ref()) {
+                            relative_path.set_extension("");
+                        }
+                        asset_server.reload_with_dependents(source, &relative_path);
                     }
                 }
                 changed.extend(paths);
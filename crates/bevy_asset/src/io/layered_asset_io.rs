@@ -0,0 +1,216 @@
+use crate::{AssetIo, AssetIoError, AssetReader, AssetWriter};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Combines several [AssetIo] layers into one. Layers are checked in the order they were added, so the first layer has
+/// the highest priority. This makes it possible to overlay mod folders on top of the base assets.
+#[derive(Default)]
+pub struct LayeredAssetIo {
+    layers: Vec<Box<dyn AssetIo>>,
+}
+
+impl LayeredAssetIo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer with a lower priority than the layers that were added before it
+    pub fn with_layer<T: AssetIo>(mut self, layer: T) -> Self {
+        self.add_layer(layer);
+        self
+    }
+
+    /// Adds a layer with a lower priority than the layers that were added before it
+    pub fn add_layer<T: AssetIo>(&mut self, layer: T) {
+        self.layers.push(Box::new(layer));
+    }
+
+    pub fn layers(&self) -> impl Iterator<Item = &dyn AssetIo> {
+        self.layers.iter().map(|layer| &**layer)
+    }
+}
+
+impl AssetIo for LayeredAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            for layer in self.layers.iter() {
+                match layer.load_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            for layer in self.layers.iter() {
+                match layer.read_path(path).await {
+                    Err(AssetIoError::NotFound(_)) => continue,
+                    result => return result,
+                }
+            }
+
+            Err(AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut children = HashSet::default();
+        let mut found = false;
+        for layer in self.layers.iter() {
+            if layer.is_directory(path) {
+                children.extend(layer.read_directory(path)?);
+                found = true;
+            }
+        }
+
+        if !found {
+            return Err(AssetIoError::NotFound(path.to_owned()));
+        }
+
+        Ok(Box::new(children.into_iter()))
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        self.layers.iter().any(|layer| layer.is_directory(path))
+    }
+
+    /// Watches the path in every layer. The path usually only exists in some layers, so this only fails if no layer
+    /// could watch it.
+    fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError> {
+        let mut watched = false;
+        let mut first_error = None;
+        for layer in self.layers.iter() {
+            match layer.watch_path_for_changes(path) {
+                Ok(()) => watched = true,
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if !watched => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        for layer in self.layers.iter() {
+            layer.watch_for_changes()?;
+        }
+
+        Ok(())
+    }
+
+    fn as_writer(&self) -> Option<&dyn AssetWriter> {
+        if self.layers.iter().any(|layer| layer.as_writer().is_some()) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+/// Saves assets to the highest priority layer that can be written to
+impl AssetWriter for LayeredAssetIo {
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            match self.layers.iter().find_map(|layer| layer.as_writer()) {
+                Some(writer) => writer.save_path(path, bytes).await,
+                None => Err(AssetIoError::ReadOnly(path.to_owned())),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayeredAssetIo;
+    use crate::{AssetIo, AssetIoError, MemoryAssetIo};
+    use futures_lite::{future, AsyncReadExt, AsyncSeekExt};
+    use std::{
+        io::SeekFrom,
+        path::{Path, PathBuf},
+    };
+
+    fn layered() -> LayeredAssetIo {
+        LayeredAssetIo::new()
+            .with_layer(
+                MemoryAssetIo::new()
+                    .with_asset("textures/player.png", "mod player")
+                    .with_asset("textures/mod/hat.png", "mod hat"),
+            )
+            .with_layer(
+                MemoryAssetIo::new()
+                    .with_asset("textures/player.png", "base player")
+                    .with_asset("textures/enemy.png", "base enemy")
+                    .with_asset("fonts/mono.ttf", "base font"),
+            )
+    }
+
+    #[test]
+    fn load_path_prefers_earlier_layers() {
+        let asset_io = layered();
+        let load = |path: &str| future::block_on(asset_io.load_path(Path::new(path)));
+
+        assert_eq!(load("textures/player.png").unwrap(), b"mod player");
+        assert_eq!(load("textures/enemy.png").unwrap(), b"base enemy");
+        assert!(matches!(
+            load("textures/missing.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn read_path_prefers_earlier_layers() {
+        let asset_io = layered();
+        let mut reader =
+            future::block_on(asset_io.read_path(Path::new("textures/player.png"))).unwrap();
+        let mut bytes = Vec::new();
+        future::block_on(async {
+            reader.seek(SeekFrom::Start(4)).await.unwrap();
+            reader.read_to_end(&mut bytes).await.unwrap();
+        });
+        assert_eq!(bytes, b"player");
+        assert!(future::block_on(asset_io.read_path(Path::new("textures/missing.png"))).is_err());
+    }
+
+    #[test]
+    fn read_directory_merges_layers() {
+        let asset_io = layered();
+        let mut children = asset_io
+            .read_directory(Path::new("textures"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+
+        assert_eq!(
+            children,
+            vec![
+                PathBuf::from("textures/enemy.png"),
+                PathBuf::from("textures/mod"),
+                PathBuf::from("textures/player.png"),
+            ]
+        );
+        assert!(asset_io.is_directory(Path::new("textures/mod")));
+        assert!(asset_io.is_directory(Path::new("fonts")));
+        assert!(!asset_io.is_directory(Path::new("fonts/mono.ttf")));
+        assert!(asset_io.read_directory(Path::new("shaders")).is_err());
+    }
+}
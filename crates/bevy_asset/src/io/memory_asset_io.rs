@@ -0,0 +1,163 @@
+use crate::{AssetIo, AssetIoError, AssetReader, AssetWriter};
+use anyhow::Result;
+use bevy_ecs::bevy_utils::BoxedFuture;
+use bevy_utils::{HashMap, HashSet};
+use futures_lite::io::Cursor;
+use parking_lot::RwLock;
+use std::{
+    borrow::Cow,
+    path::{Component, Path, PathBuf},
+};
+
+/// Serves assets from memory. Directories are implied by the paths of the stored assets.
+/// This is useful in tests and for assets that are generated at runtime.
+#[derive(Default)]
+pub struct MemoryAssetIo {
+    assets: RwLock<HashMap<PathBuf, Cow<'static, [u8]>>>,
+}
+
+impl MemoryAssetIo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_asset<P: AsRef<Path>, B: Into<Vec<u8>>>(self, path: P, bytes: B) -> Self {
+        self.insert(path, bytes);
+        self
+    }
+
+    /// Adds an asset at the given path, replacing any asset that is already stored there
+    pub fn insert<P: AsRef<Path>, B: Into<Vec<u8>>>(&self, path: P, bytes: B) {
+        self.assets
+            .write()
+            .insert(normalize_path(path.as_ref()), Cow::Owned(bytes.into()));
+    }
+
+    /// Adds an asset at the given path without copying its bytes, replacing any asset that is already stored there
+    pub fn insert_static<P: AsRef<Path>>(&self, path: P, bytes: &'static [u8]) {
+        self.assets
+            .write()
+            .insert(normalize_path(path.as_ref()), Cow::Borrowed(bytes));
+    }
+
+    pub fn remove<P: AsRef<Path>>(&self, path: P) -> Option<Vec<u8>> {
+        self.assets
+            .write()
+            .remove(&normalize_path(path.as_ref()))
+            .map(|bytes| bytes.into_owned())
+    }
+
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.assets
+            .read()
+            .contains_key(&normalize_path(path.as_ref()))
+    }
+}
+
+impl AssetIo for MemoryAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            self.assets
+                .read()
+                .get(&normalize_path(path))
+                .map(|bytes| bytes.to_vec())
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    /// Reads the stored bytes without copying them if they are static, as they are for embedded assets
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            self.assets
+                .read()
+                .get(&normalize_path(path))
+                .map(|bytes| Box::new(Cursor::new(bytes.clone())) as Box<dyn AssetReader>)
+                .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let assets = self.assets.read();
+        read_directory_from_paths(assets.keys(), path)
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        is_directory_in_paths(self.assets.read().keys(), path)
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn as_writer(&self) -> Option<&dyn AssetWriter> {
+        Some(self)
+    }
+}
+
+impl AssetWriter for MemoryAssetIo {
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>> {
+        Box::pin(async move {
+            self.insert(path, bytes);
+            Ok(())
+        })
+    }
+}
+
+/// Strips `.` and root components so that `./textures/a.png`, `/textures/a.png` and `textures/a.png` refer to the
+/// same asset
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect()
+}
+
+/// Lists the direct children of `directory`, given the paths of every file in an [AssetIo] that has no real
+/// directories
+pub(crate) fn read_directory_from_paths<'a>(
+    file_paths: impl Iterator<Item = &'a PathBuf>,
+    directory: &Path,
+) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+    let directory = normalize_path(directory);
+    let mut children = HashSet::default();
+    for file_path in file_paths {
+        if let Ok(relative_path) = file_path.strip_prefix(&directory) {
+            if let Some(child) = relative_path.components().next() {
+                children.insert(directory.join(child));
+            }
+        }
+    }
+
+    if children.is_empty() {
+        return Err(AssetIoError::NotFound(directory));
+    }
+
+    Ok(Box::new(children.into_iter()))
+}
+
+pub(crate) fn is_directory_in_paths<'a>(
+    mut file_paths: impl Iterator<Item = &'a PathBuf>,
+    directory: &Path,
+) -> bool {
+    let directory = normalize_path(directory);
+    file_paths.any(|file_path| {
+        file_path
+            .strip_prefix(&directory)
+            .map_or(false, |relative_path| {
+                relative_path.components().next().is_some()
+            })
+    })
+}
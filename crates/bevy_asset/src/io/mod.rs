@@ -1,19 +1,29 @@
+#[cfg(all(any(feature = "zip", feature = "tar"), not(target_arch = "wasm32")))]
+mod archive_asset_io;
 #[cfg(not(target_arch = "wasm32"))]
 mod file_asset_io;
+mod layered_asset_io;
+mod memory_asset_io;
 #[cfg(target_arch = "wasm32")]
 mod wasm_asset_io;
 
+#[cfg(all(any(feature = "zip", feature = "tar"), not(target_arch = "wasm32")))]
+pub use archive_asset_io::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use file_asset_io::*;
+pub use layered_asset_io::*;
+pub use memory_asset_io::MemoryAssetIo;
 #[cfg(target_arch = "wasm32")]
 pub use wasm_asset_io::*;
 
 use anyhow::Result;
 use bevy_ecs::bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::io::{AsyncRead, AsyncSeek, Cursor};
 use std::{
     io,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 use thiserror::Error;
 
@@ -26,18 +36,62 @@ pub enum AssetIoError {
     Io(#[from] io::Error),
     #[error("Failed to watch path")]
     PathWatchError(PathBuf),
+    #[error("Unsupported archive format. Archives must be .zip or .tar files with the matching feature enabled.")]
+    UnsupportedArchiveFormat(PathBuf),
+    #[error("The asset source can not be written to.")]
+    ReadOnly(PathBuf),
 }
 
+/// A seekable reader over the bytes of an asset. This lets loaders read large assets piece by piece instead of holding
+/// the whole asset in memory.
+pub trait AssetReader: AsyncRead + AsyncSeek + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncSeek + Unpin + Send> AssetReader for T {}
+
 /// Handles load requests from an AssetServer
 pub trait AssetIo: Downcast + Send + Sync + 'static {
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
+
+    /// Returns a reader over the asset at the given path. The default implementation reads the whole asset into memory,
+    /// so [AssetIo]s that can read assets incrementally should override it.
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes = self.load_path(path).await?;
+            Ok(Box::new(Cursor::new(bytes)) as Box<dyn AssetReader>)
+        })
+    }
     fn read_directory(
         &self,
         path: &Path,
     ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError>;
     fn is_directory(&self, path: &Path) -> bool;
+
+    /// Returns when the asset at the given path was last modified, if this [AssetIo] can tell without reading it. This
+    /// lets the [AssetServer](crate::AssetServer) skip hashing sources that haven't changed since they were processed.
+    fn modified(&self, _path: &Path) -> Option<SystemTime> {
+        None
+    }
+
     fn watch_path_for_changes(&self, path: &Path) -> Result<(), AssetIoError>;
     fn watch_for_changes(&self) -> Result<(), AssetIoError>;
+
+    /// Returns this [AssetIo] as an [AssetWriter] if it can write assets
+    fn as_writer(&self) -> Option<&dyn AssetWriter> {
+        None
+    }
 }
 
 impl_downcast!(AssetIo);
+
+/// An [AssetIo] that assets can be saved to
+pub trait AssetWriter: AssetIo {
+    /// Writes the given bytes to the given path, replacing any asset that is already there
+    fn save_path<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<'a, Result<(), AssetIoError>>;
+}
@@ -1,21 +1,31 @@
 mod asset_server;
 mod assets;
+mod diagnostics;
+mod embedded;
 #[cfg(all(feature = "filesystem_watcher", not(target_arch = "wasm32")))]
 mod filesystem_watcher;
 mod handle;
 mod info;
 mod io;
 mod loader;
+mod loading_tracker;
 mod path;
+mod processor;
+mod saver;
 
 pub use asset_server::*;
 pub use assets::*;
 use bevy_tasks::IoTaskPool;
+pub use diagnostics::*;
+pub use embedded::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
+pub use loading_tracker::*;
 pub use path::*;
+pub use processor::*;
+pub use saver::*;
 
 /// The names of asset stages in an App Schedule
 pub mod stage {
@@ -38,12 +48,15 @@ pub struct AssetPlugin;
 
 pub struct AssetServerSettings {
     pub asset_folder: String,
+    /// The folder processed assets are read from. See [AssetServer::process_assets].
+    pub processed_asset_folder: Option<String>,
 }
 
 impl Default for AssetServerSettings {
     fn default() -> Self {
         Self {
             asset_folder: "assets".to_string(),
+            processed_asset_folder: None,
         }
     }
 }
@@ -57,6 +70,7 @@ impl Plugin for AssetPlugin {
             .0
             .clone();
 
+        let embedded_assets = EmbeddedAssets::default();
         let asset_server = {
             let settings = app
                 .resources_mut()
@@ -66,16 +80,43 @@ impl Plugin for AssetPlugin {
             let source = FileAssetIo::new(&settings.asset_folder);
             #[cfg(target_arch = "wasm32")]
             let source = WasmAssetIo::new(&settings.asset_folder);
-            AssetServer::new(source, task_pool)
+            let source = LayeredAssetIo::new()
+                .with_layer(source)
+                .with_layer(embedded_assets.clone());
+            let asset_server = AssetServer::new(source, task_pool);
+
+            if let Some(ref processed_asset_folder) = settings.processed_asset_folder {
+                #[cfg(not(target_arch = "wasm32"))]
+                asset_server.set_processed_asset_io(FileAssetIo::new(processed_asset_folder));
+                #[cfg(target_arch = "wasm32")]
+                asset_server.set_processed_asset_io(WasmAssetIo::new(processed_asset_folder));
+            }
+            asset_server
         };
+        asset_server
+            .add_asset_source(EMBEDDED_ASSET_SOURCE, embedded_assets.clone())
+            .unwrap();
 
         app.add_stage_before(bevy_app::stage::PRE_UPDATE, stage::LOAD_ASSETS)
             .add_stage_after(bevy_app::stage::POST_UPDATE, stage::ASSET_EVENTS)
             .add_resource(asset_server)
+            .add_resource(embedded_assets)
+            .init_resource::<AssetDiagnostics>()
+            .init_resource::<LoadingTracker>()
+            .add_event::<LoadingGroupFinished>()
+            .add_event::<AssetLoadFailed>()
             .register_property::<HandleId>()
             .add_system_to_stage(
                 bevy_app::stage::PRE_UPDATE,
                 asset_server::free_unused_assets_system.system(),
+            )
+            .add_system_to_stage(
+                stage::ASSET_EVENTS,
+                asset_server::asset_load_failed_system.system(),
+            )
+            .add_system_to_stage(
+                stage::ASSET_EVENTS,
+                loading_tracker::loading_tracker_system.system(),
             );
 
         #[cfg(all(feature = "filesystem_watcher", not(target_arch = "wasm32")))]
@@ -1,6 +1,6 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    RefChangeChannel,
+    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetReader, AssetServer, Assets, Handle,
+    HandleId, RefChangeChannel,
 };
 use anyhow::Result;
 use bevy_ecs::{Res, ResMut, Resource};
@@ -8,7 +8,37 @@ use bevy_type_registry::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
-use std::path::Path;
+use futures_lite::AsyncReadExt;
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+/// The extension of the sidecar files that hold an asset's loader settings, as in `textures/player.png.meta`
+pub const META_EXTENSION: &str = "meta";
+
+/// Returns the path of the sidecar file that holds the loader settings of the asset at the given path
+pub fn get_meta_path(path: &Path) -> PathBuf {
+    let mut file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_os_string())
+        .unwrap_or_default();
+    file_name.push(".");
+    file_name.push(META_EXTENSION);
+    path.with_file_name(file_name)
+}
+
+/// Loader settings read from an asset's [AssetPath] or its `.meta` file. See [AssetLoader::deserialize_settings].
+pub trait LoaderSettings: Downcast + Send + Sync + 'static {}
+impl_downcast!(LoaderSettings);
+
+impl<T> LoaderSettings for T where T: Send + Sync + 'static {}
+
+/// Deserializes loader settings of type `T` from RON. Loaders that take settings return this from
+/// [AssetLoader::deserialize_settings].
+pub fn deserialize_settings<T: DeserializeOwned + LoaderSettings>(
+    settings: &str,
+) -> Result<Box<dyn LoaderSettings>, anyhow::Error> {
+    Ok(Box::new(ron::de::from_str::<T>(settings)?))
+}
 
 /// A loader for an asset source
 pub trait AssetLoader: Send + Sync + 'static {
@@ -18,6 +48,33 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>>;
     fn extensions(&self) -> &[&str];
+
+    /// Deserializes the loader settings of an asset, which are given in RON in its [AssetPath] or its `.meta` file.
+    /// Loaders that take settings implement this with [deserialize_settings] for their settings type, so that settings
+    /// meant for another loader fail the load. Loaders take no settings by default.
+    fn deserialize_settings(
+        &self,
+        settings: &str,
+    ) -> Result<Box<dyn LoaderSettings>, anyhow::Error> {
+        Err(anyhow::anyhow!(
+            "this loader does not take settings, but was given {}",
+            settings
+        ))
+    }
+
+    /// Loads the asset from a reader over its bytes. Loaders for large assets can override this to read only the parts
+    /// they need. The default implementation reads the whole asset and passes its bytes to [AssetLoader::load].
+    fn load_from_reader<'a>(
+        &'a self,
+        reader: &'a mut dyn AssetReader,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            self.load(&bytes, load_context).await
+        })
+    }
 }
 
 pub trait Asset: TypeUuid + AssetDynamic {}
@@ -57,13 +114,25 @@ pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
     pub(crate) labeled_assets: HashMap<Option<String>, LoadedAsset>,
+    pub(crate) source: Option<&'a str>,
     pub(crate) path: &'a Path,
+    pub(crate) loader_settings: Option<&'a str>,
+    pub(crate) settings: Option<LoadContextSettings<'a>>,
     pub(crate) version: usize,
 }
 
+/// The settings a loader uses for an asset, which come from its [AssetPath] or from its meta file
+pub(crate) struct LoadContextSettings<'a> {
+    pub ron: &'a str,
+    pub value: &'a dyn LoaderSettings,
+}
+
 impl<'a> LoadContext<'a> {
+    /// The settings in `asset_path` are part of the asset's id. `settings` are the settings the loader should use,
+    /// which may instead come from the asset's meta file.
     pub(crate) fn new(
-        path: &'a Path,
+        asset_path: &'a AssetPath<'a>,
+        settings: Option<LoadContextSettings<'a>>,
         ref_change_channel: &'a RefChangeChannel,
         asset_io: &'a dyn AssetIo,
         version: usize,
@@ -73,7 +142,10 @@ impl<'a> LoadContext<'a> {
             asset_io,
             labeled_assets: Default::default(),
             version,
-            path,
+            source: asset_path.source(),
+            path: asset_path.path(),
+            loader_settings: asset_path.loader_settings(),
+            settings,
         }
     }
 
@@ -81,6 +153,42 @@ impl<'a> LoadContext<'a> {
         &self.path
     }
 
+    /// The name of the asset source the asset is being loaded from, or `None` for the default source
+    pub fn source(&self) -> Option<&str> {
+        self.source
+    }
+
+    /// The loader settings of the asset being loaded, in RON. These come from the asset's [AssetPath] if it has
+    /// settings, and from the asset's meta file otherwise.
+    pub fn settings_ron(&self) -> Option<&str> {
+        self.settings.as_ref().map(|settings| settings.ron)
+    }
+
+    /// The loader settings of the asset being loaded, as deserialized by [AssetLoader::deserialize_settings]. Returns
+    /// `None` if the asset has no settings, or if they are not a `T`.
+    pub fn settings<T: LoaderSettings>(&self) -> Option<&T> {
+        self.settings
+            .as_ref()
+            .and_then(|settings| settings.value.downcast_ref::<T>())
+    }
+
+    /// Returns the [AssetPath] of the labeled asset with the given label in the asset being loaded
+    pub fn labeled_asset_path<'b>(&'b self, label: &'b str) -> AssetPath<'b> {
+        AssetPath::new_ref(self.path, Some(label))
+            .with_optional_source(self.source)
+            .with_optional_loader_settings(self.loader_settings)
+    }
+
+    /// Returns an [AssetPath] in the same asset source as the asset being loaded. Loaders should use this for
+    /// dependencies so that they resolve against the right source.
+    pub fn get_asset_path(&self, path: PathBuf, label: Option<String>) -> AssetPath<'static> {
+        let asset_path = AssetPath::new(path, label);
+        match self.source {
+            Some(source) => asset_path.with_source(source.to_string()),
+            None => asset_path,
+        }
+    }
+
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
     }
@@ -102,6 +210,14 @@ impl<'a> LoadContext<'a> {
         self.asset_io.load_path(path.as_ref()).await
     }
 
+    /// Returns a reader over the asset at the given path, for loaders that only need parts of another asset
+    pub async fn read_asset<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        self.asset_io.read_path(path.as_ref()).await
+    }
+
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
         let mut asset_metas = Vec::new();
         for (label, asset) in self.labeled_assets.iter() {
@@ -0,0 +1,225 @@
+use crate::{AssetReader, AssetServer, HandleId, LoadState, SourcePathId};
+use bevy_app::prelude::Events;
+use bevy_ecs::{Res, ResMut};
+use bevy_utils::{HashMap, HashSet};
+use futures_lite::io::{AsyncRead, AsyncSeek};
+use std::{
+    io::{self, SeekFrom},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+/// The progress of a group of assets tracked by [LoadingTracker]. Counts include the recursive dependencies of the
+/// tracked assets, so `total` can grow while dependencies are discovered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadingProgress {
+    pub loaded: usize,
+    pub failed: usize,
+    pub total: usize,
+    /// The number of bytes read from asset sources for the assets in the group so far
+    pub bytes_read: usize,
+}
+
+impl LoadingProgress {
+    /// Returns true if every asset in the group has either loaded or failed
+    pub fn is_finished(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+
+    /// The fraction of assets in the group that have either loaded or failed, between 0 and 1
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        }
+    }
+}
+
+/// Sent when every asset in a [LoadingTracker] group has either loaded or failed
+#[derive(Debug, Clone)]
+pub struct LoadingGroupFinished {
+    pub group: String,
+    pub progress: LoadingProgress,
+}
+
+#[derive(Debug, Default)]
+struct LoadingGroup {
+    handles: Vec<HandleId>,
+    progress: LoadingProgress,
+    finished: bool,
+}
+
+/// Tracks the loading progress of named groups of assets, for example to drive a loading screen. Progress is updated
+/// once per frame, and a [LoadingGroupFinished] event is sent when a group finishes.
+#[derive(Debug, Default)]
+pub struct LoadingTracker {
+    groups: HashMap<String, LoadingGroup>,
+}
+
+impl LoadingTracker {
+    /// Adds the given asset to the given group, creating the group if it does not exist
+    pub fn add<H: Into<HandleId>>(&mut self, group: &str, handle: H) {
+        let group = self.groups.entry(group.to_string()).or_default();
+        group.handles.push(handle.into());
+        group.finished = false;
+    }
+
+    pub fn add_all<H: Into<HandleId>>(
+        &mut self,
+        group: &str,
+        handles: impl IntoIterator<Item = H>,
+    ) {
+        for handle in handles {
+            self.add(group, handle);
+        }
+    }
+
+    /// Stops tracking the given group
+    pub fn remove_group(&mut self, group: &str) {
+        self.groups.remove(group);
+    }
+
+    pub fn get_progress(&self, group: &str) -> Option<LoadingProgress> {
+        self.groups.get(group).map(|group| group.progress)
+    }
+
+    pub fn is_finished(&self, group: &str) -> bool {
+        self.groups.get(group).map_or(false, |group| group.finished)
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &str> {
+        self.groups.keys().map(|group| group.as_str())
+    }
+
+    fn update(&mut self, asset_server: &AssetServer, events: &mut Events<LoadingGroupFinished>) {
+        for (name, group) in self.groups.iter_mut() {
+            group.progress = get_progress(asset_server, &group.handles);
+            if !group.finished && group.progress.is_finished() {
+                group.finished = true;
+                events.send(LoadingGroupFinished {
+                    group: name.clone(),
+                    progress: group.progress,
+                });
+            }
+        }
+    }
+}
+
+fn get_progress(asset_server: &AssetServer, handles: &[HandleId]) -> LoadingProgress {
+    let asset_sources = asset_server.server.asset_sources.read();
+    let mut progress = LoadingProgress::default();
+    let mut visited = HashSet::default();
+    let mut pending = Vec::new();
+    for handle in handles {
+        match handle {
+            HandleId::AssetPathId(id) => pending.push(id.source_path_id()),
+            // assets that were not loaded from a path are available as soon as they have a handle
+            HandleId::Id(_, _) => {
+                progress.loaded += 1;
+                progress.total += 1;
+            }
+        }
+    }
+
+    while let Some(source_path_id) = pending.pop() {
+        if !visited.insert(source_path_id) {
+            continue;
+        }
+
+        progress.total += 1;
+        let source_info = match asset_sources.get(&source_path_id) {
+            Some(source_info) => source_info,
+            // the asset has been queued but has not started loading yet
+            None => continue,
+        };
+        match source_info.load_state {
+            LoadState::Loaded => progress.loaded += 1,
+            LoadState::Failed => progress.failed += 1,
+            LoadState::NotLoaded | LoadState::Loading => {}
+        }
+        progress.bytes_read += source_info.bytes_read();
+        pending.extend(source_info.dependencies().map(SourcePathId::from));
+    }
+
+    progress
+}
+
+pub fn loading_tracker_system(
+    asset_server: Res<AssetServer>,
+    mut loading_tracker: ResMut<LoadingTracker>,
+    mut events: ResMut<Events<LoadingGroupFinished>>,
+) {
+    loading_tracker.update(&asset_server, &mut events);
+}
+
+/// Wraps an [AssetReader] to count the bytes read through it
+pub(crate) struct CountingReader {
+    pub(crate) reader: Box<dyn AssetReader>,
+    pub(crate) bytes_read: Arc<AtomicUsize>,
+}
+
+impl AsyncRead for CountingReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let result = Pin::new(&mut *self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(read)) = result {
+            self.bytes_read.fetch_add(read, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+impl AsyncSeek for CountingReader {
+    fn poll_seek(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        Pin::new(&mut *self.reader).poll_seek(cx, pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoadingGroupFinished, LoadingTracker};
+    use crate::{AssetServer, LoadState, MemoryAssetIo};
+    use bevy_app::prelude::Events;
+    use bevy_tasks::TaskPool;
+
+    #[test]
+    fn groups_count_dependencies_and_finish_once() {
+        let asset_server = AssetServer::new(MemoryAssetIo::new(), TaskPool::new());
+        let mut tracker = LoadingTracker::default();
+        let mut events = Events::<LoadingGroupFinished>::default();
+        let mut reader = events.get_reader();
+        tracker.add("level", "level.scn");
+        tracker.add("level", "music.ogg");
+
+        asset_server.insert_test_source("level.scn", LoadState::Loaded, &["tiles.png"]);
+        asset_server.insert_test_source("music.ogg", LoadState::Failed, &[]);
+        tracker.update(&asset_server, &mut events);
+        let progress = tracker.get_progress("level").unwrap();
+        assert_eq!(
+            (progress.loaded, progress.failed, progress.total),
+            (1, 1, 3)
+        );
+        assert!(!tracker.is_finished("level"));
+
+        asset_server.insert_test_source("tiles.png", LoadState::Loaded, &[]);
+        tracker.update(&asset_server, &mut events);
+        tracker.update(&asset_server, &mut events);
+        assert!(tracker.is_finished("level"));
+        let finished = reader.iter(&events).collect::<Vec<_>>();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].group, "level");
+        assert_eq!(finished[0].progress.total, 3);
+    }
+}
@@ -3,33 +3,91 @@ use bevy_utils::AHasher;
 use serde::{Deserialize, Serialize};
 use std::{
     borrow::Cow,
+    fmt,
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
 };
 
-#[derive(Debug, Hash, Clone, Serialize, Deserialize)]
+/// The separator between an asset source name and the path within that source, as in `mods://textures/foo.png`
+pub const ASSET_SOURCE_SEPARATOR: &str = "://";
+
+/// A path to an asset, made up of an optional asset source name, a path within that source, and an optional label.
+/// Paths without a source are loaded from the AssetServer's default source.
+///
+/// A path may also carry loader settings in RON. The same file loaded with different settings has a different
+/// [AssetPathId], so it produces distinct assets.
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    source: Option<Cow<'a, str>>,
     path: Cow<'a, Path>,
     label: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    loader_settings: Option<Cow<'a, str>>,
 }
 
 impl<'a> AssetPath<'a> {
     #[inline]
     pub fn new_ref(path: &'a Path, label: Option<&'a str>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: label.map(|val| Cow::Borrowed(val)),
+            loader_settings: None,
         }
     }
 
     #[inline]
     pub fn new(path: PathBuf, label: Option<String>) -> AssetPath<'a> {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: label.map(Cow::Owned),
+            loader_settings: None,
         }
     }
 
+    /// Returns this path in the asset source with the given name
+    #[inline]
+    pub fn with_source<S: Into<Cow<'a, str>>>(mut self, source: S) -> AssetPath<'a> {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns this path in the given asset source, or in the default source if `source` is `None`
+    #[inline]
+    pub fn with_optional_source(mut self, source: Option<&'a str>) -> AssetPath<'a> {
+        self.source = source.map(Cow::Borrowed);
+        self
+    }
+
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_ref().map(|source| source.as_ref())
+    }
+
+    /// Returns this path with the given loader settings, serialized as RON
+    #[inline]
+    pub fn with_loader_settings<S: Into<Cow<'a, str>>>(mut self, settings: S) -> AssetPath<'a> {
+        self.loader_settings = Some(settings.into());
+        self
+    }
+
+    /// Returns this path with the given loader settings, or without settings if `settings` is `None`
+    #[inline]
+    pub fn with_optional_loader_settings(mut self, settings: Option<&'a str>) -> AssetPath<'a> {
+        self.loader_settings = settings.map(Cow::Borrowed);
+        self
+    }
+
+    /// The loader settings of this path, serialized as RON
+    #[inline]
+    pub fn loader_settings(&self) -> Option<&str> {
+        self.loader_settings
+            .as_ref()
+            .map(|settings| settings.as_ref())
+    }
+
     #[inline]
     pub fn get_id(&self) -> AssetPathId {
         AssetPathId::from(self)
@@ -48,11 +106,19 @@ impl<'a> AssetPath<'a> {
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
         AssetPath {
+            source: self
+                .source
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
             path: Cow::Owned(self.path.to_path_buf()),
             label: self
                 .label
                 .as_ref()
                 .map(|value| Cow::Owned(value.to_string())),
+            loader_settings: self
+                .loader_settings
+                .as_ref()
+                .map(|value| Cow::Owned(value.to_string())),
         }
     }
 }
@@ -80,6 +146,24 @@ impl<'a> From<&'a Path> for SourcePathId {
     }
 }
 
+impl<'a, 'b> From<&'a AssetPath<'b>> for SourcePathId {
+    fn from(asset_path: &'a AssetPath<'b>) -> Self {
+        if asset_path.source().is_none() && asset_path.loader_settings().is_none() {
+            return SourcePathId::from(asset_path.path());
+        }
+
+        let mut hasher = get_hasher();
+        if let Some(source) = asset_path.source() {
+            source.hash(&mut hasher);
+        }
+        asset_path.path().hash(&mut hasher);
+        if let Some(loader_settings) = asset_path.loader_settings() {
+            loader_settings.hash(&mut hasher);
+        }
+        SourcePathId(hasher.finish())
+    }
+}
+
 impl From<AssetPathId> for SourcePathId {
     fn from(id: AssetPathId) -> Self {
         id.source_path_id()
@@ -122,7 +206,7 @@ where
     fn from(value: T) -> Self {
         let asset_path: AssetPath = value.into();
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::from(&asset_path),
             LabelId::from(asset_path.label()),
         )
     }
@@ -131,29 +215,52 @@ where
 impl<'a, 'b> From<&'a AssetPath<'b>> for AssetPathId {
     fn from(asset_path: &'a AssetPath<'b>) -> Self {
         AssetPathId(
-            SourcePathId::from(asset_path.path()),
+            SourcePathId::from(asset_path),
             LabelId::from(asset_path.label()),
         )
     }
 }
 
+/// Splits `source://path#label` into its parts
+fn parse_asset_path(asset_path: &str) -> (Option<&str>, &str, Option<&str>) {
+    let (source, path) = match asset_path.find(ASSET_SOURCE_SEPARATOR) {
+        Some(index) => (
+            Some(&asset_path[..index]),
+            &asset_path[index + ASSET_SOURCE_SEPARATOR.len()..],
+        ),
+        None => (None, asset_path),
+    };
+    let mut parts = path.split('#');
+    let path = parts.next().expect("path must be set");
+    let label = parts.next();
+    (source, path, label)
+}
+
 impl<'a> From<&'a str> for AssetPath<'a> {
     fn from(asset_path: &'a str) -> Self {
-        let mut parts = asset_path.split('#');
-        let path = Path::new(parts.next().expect("path must be set"));
-        let label = parts.next();
+        let (source, path, label) = parse_asset_path(asset_path);
         AssetPath {
-            path: Cow::Borrowed(path),
-            label: label.map(|label| Cow::Borrowed(label)),
+            source: source.map(Cow::Borrowed),
+            path: Cow::Borrowed(Path::new(path)),
+            label: label.map(Cow::Borrowed),
+            loader_settings: None,
         }
     }
 }
 
+impl<'a> From<String> for AssetPath<'a> {
+    fn from(asset_path: String) -> Self {
+        AssetPath::from(asset_path.as_str()).to_owned()
+    }
+}
+
 impl<'a> From<&'a Path> for AssetPath<'a> {
     fn from(path: &'a Path) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Borrowed(path),
             label: None,
+            loader_settings: None,
         }
     }
 }
@@ -161,8 +268,81 @@ impl<'a> From<&'a Path> for AssetPath<'a> {
 impl<'a> From<PathBuf> for AssetPath<'a> {
     fn from(path: PathBuf) -> Self {
         AssetPath {
+            source: None,
             path: Cow::Owned(path),
             label: None,
+            loader_settings: None,
+        }
+    }
+}
+
+/// Formats the path as `source://path#label`, which [AssetPath::from] parses back. Loader settings are not included.
+impl<'a> fmt::Display for AssetPath<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(source) = self.source() {
+            write!(f, "{}{}", source, ASSET_SOURCE_SEPARATOR)?;
+        }
+        write!(f, "{}", self.path.display())?;
+        if let Some(label) = self.label() {
+            write!(f, "#{}", label)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AssetPath, AssetPathId};
+    use std::path::Path;
+
+    #[test]
+    fn parse_source_qualified_path() {
+        let asset_path = AssetPath::from("mods://textures/foo.png#normal");
+        assert_eq!(asset_path.source(), Some("mods"));
+        assert_eq!(asset_path.path(), Path::new("textures/foo.png"));
+        assert_eq!(asset_path.label(), Some("normal"));
+
+        let asset_path = AssetPath::from("textures/foo.png");
+        assert_eq!(asset_path.source(), None);
+        assert_eq!(asset_path.path(), Path::new("textures/foo.png"));
+        assert_eq!(asset_path.label(), None);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for path in &["mods://textures/foo.png#normal", "textures/foo.png"] {
+            assert_eq!(AssetPath::from(*path).to_string(), *path);
         }
     }
+
+    #[test]
+    fn sources_produce_distinct_ids() {
+        let base = AssetPathId::from("textures/foo.png");
+        let mods = AssetPathId::from("mods://textures/foo.png");
+        assert_ne!(base, mods);
+        assert_eq!(mods, AssetPathId::from("mods://textures/foo.png"));
+        assert_eq!(
+            base,
+            AssetPathId::from(AssetPath::new_ref(Path::new("textures/foo.png"), None))
+        );
+    }
+
+    #[test]
+    fn loader_settings_produce_distinct_ids() {
+        let default = AssetPathId::from("textures/foo.png");
+        let nearest = AssetPathId::from(
+            AssetPath::from("textures/foo.png").with_loader_settings("(filter:Nearest)"),
+        );
+        let linear = AssetPathId::from(
+            AssetPath::from("textures/foo.png").with_loader_settings("(filter:Linear)"),
+        );
+        assert_ne!(default, nearest);
+        assert_ne!(nearest, linear);
+        assert_eq!(
+            nearest,
+            AssetPathId::from(
+                AssetPath::from("textures/foo.png").with_loader_settings("(filter:Nearest)")
+            )
+        );
+    }
 }
@@ -0,0 +1,217 @@
+use anyhow::Result;
+use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The extension of the files that record how a processed asset was produced
+pub const PROCESSED_META_EXTENSION: &str = "processed.ron";
+
+/// Transforms source assets into a format that is faster to load, ahead of time.
+///
+/// Processed outputs are written by [AssetServer::process_assets](crate::AssetServer::process_assets) and are loaded
+/// in place of their source assets by the [AssetLoader](crate::AssetLoader) registered for
+/// [AssetProcessor::output_extension].
+pub trait AssetProcessor: Send + Sync + 'static {
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>>;
+
+    /// The extensions of the source assets this processor handles
+    fn extensions(&self) -> &[&str];
+
+    /// The extension of the processed output
+    fn output_extension(&self) -> &str;
+
+    /// Identifies the settings and version of this processor. Processed assets are produced again when this changes.
+    fn settings(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Records how a processed asset was produced. This is stored next to the processed output in a
+/// [PROCESSED_META_EXTENSION] file, and in the [SourceMeta](crate::SourceMeta) of assets loaded from processed outputs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProcessedMeta {
+    /// A hash of the source asset's bytes and the processor's name and settings
+    pub hash: u64,
+    pub processor: String,
+    /// The path of the processed output, relative to the processed asset folder
+    pub output_path: PathBuf,
+    /// When the source asset was last modified before it was processed, if its [AssetIo](crate::AssetIo) can tell
+    #[serde(default)]
+    pub source_modified: Option<SystemTime>,
+}
+
+impl ProcessedMeta {
+    /// The path of the file that stores the [ProcessedMeta] for the given source asset path
+    pub fn meta_path(source_path: &Path) -> PathBuf {
+        let mut file_name = source_path
+            .file_name()
+            .map(|file_name| file_name.to_os_string())
+            .unwrap_or_default();
+        file_name.push(".");
+        file_name.push(PROCESSED_META_EXTENSION);
+        source_path.with_file_name(file_name)
+    }
+
+    /// The path of the processed output for the given source asset path and hash. Including the hash in the path
+    /// ensures outdated outputs are never read.
+    pub fn output_path(source_path: &Path, hash: u64, output_extension: &str) -> PathBuf {
+        let mut file_name = source_path
+            .file_name()
+            .map(|file_name| file_name.to_os_string())
+            .unwrap_or_default();
+        file_name.push(format!(".{:016x}.{}", hash, output_extension));
+        source_path.with_file_name(file_name)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::{AssetProcessor, ProcessedMeta};
+    use crate::{AssetIo, AssetPath, AssetServer, FileAssetIo, MemoryAssetIo};
+    use anyhow::Result;
+    use bevy_tasks::TaskPool;
+    use bevy_utils::BoxedFuture;
+    use futures_lite::future;
+    use std::path::{Path, PathBuf};
+
+    struct UppercaseProcessor;
+
+    impl AssetProcessor for UppercaseProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            _path: &'a Path,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            Box::pin(async move { Ok(bytes.to_ascii_uppercase()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["txt"]
+        }
+
+        fn output_extension(&self) -> &str {
+            "upper"
+        }
+    }
+
+    #[test]
+    fn process_assets_skips_unchanged_assets() {
+        let output_folder = std::env::temp_dir().join(format!(
+            "bevy_asset_processor_test_{}",
+            rand::random::<u64>()
+        ));
+        let asset_io = MemoryAssetIo::new()
+            .with_asset("text/hello.txt", "hello")
+            .with_asset("text/ignored.bin", "ignored");
+        let asset_server = AssetServer::new(asset_io, TaskPool::new());
+        asset_server.add_processor(UppercaseProcessor);
+
+        let processed = asset_server.process_assets(&output_folder).unwrap();
+        assert_eq!(processed.len(), 1);
+        let output_path = &processed[0];
+        assert_eq!(output_path.parent(), Some(Path::new("text")));
+        assert_eq!(
+            std::fs::read(output_folder.join(output_path)).unwrap(),
+            b"HELLO"
+        );
+
+        let meta_path = output_folder.join(ProcessedMeta::meta_path(Path::new("text/hello.txt")));
+        let meta: ProcessedMeta = ron::de::from_bytes(&std::fs::read(meta_path).unwrap()).unwrap();
+        assert_eq!(&meta.output_path, output_path);
+
+        assert_eq!(
+            asset_server.process_assets(&output_folder).unwrap(),
+            Vec::<PathBuf>::new()
+        );
+        std::fs::remove_dir_all(output_folder).unwrap();
+    }
+
+    #[test]
+    fn processed_outputs_of_changed_sources_are_not_loaded() {
+        let output_folder = std::env::temp_dir().join(format!(
+            "bevy_asset_processor_test_{}",
+            rand::random::<u64>()
+        ));
+        let asset_server = AssetServer::new(
+            MemoryAssetIo::new().with_asset("text/hello.txt", "hello"),
+            TaskPool::new(),
+        );
+        asset_server.add_processor(UppercaseProcessor);
+        let output_path = asset_server.process_assets(&output_folder).unwrap()[0].clone();
+        let meta_path = ProcessedMeta::meta_path(Path::new("text/hello.txt"));
+        asset_server.set_processed_asset_io(
+            MemoryAssetIo::new()
+                .with_asset(
+                    &output_path,
+                    std::fs::read(output_folder.join(&output_path)).unwrap(),
+                )
+                .with_asset(
+                    &meta_path,
+                    std::fs::read(output_folder.join(&meta_path)).unwrap(),
+                ),
+        );
+        std::fs::remove_dir_all(output_folder).unwrap();
+
+        let asset_path = AssetPath::from("text/hello.txt");
+        let unchanged = MemoryAssetIo::new().with_asset("text/hello.txt", "hello");
+        let (_, bytes) =
+            future::block_on(asset_server.load_processed(&asset_path, &unchanged)).unwrap();
+        assert_eq!(bytes, b"HELLO");
+
+        // the source was edited after it was processed, for example between runs of the app
+        let changed = MemoryAssetIo::new().with_asset("text/hello.txt", "goodbye");
+        assert!(future::block_on(asset_server.load_processed(&asset_path, &changed)).is_none());
+    }
+
+    #[test]
+    fn sources_modified_before_processing_are_not_hashed() {
+        let source_folder = std::env::temp_dir().join(format!(
+            "bevy_asset_processor_test_{}",
+            rand::random::<u64>()
+        ));
+        std::fs::create_dir_all(source_folder.join("text")).unwrap();
+        std::fs::write(source_folder.join("text/hello.txt"), "hello").unwrap();
+        let source = FileAssetIo::new(&source_folder);
+        let asset_server = AssetServer::new(MemoryAssetIo::new(), TaskPool::new());
+        asset_server.add_processor(UppercaseProcessor);
+
+        // the recorded hash never matches the source, so the output is only used if the source isn't hashed
+        let set_processed_meta = |source_modified| {
+            let processed = ProcessedMeta {
+                hash: 0,
+                processor: "UppercaseProcessor".to_string(),
+                output_path: ProcessedMeta::output_path(Path::new("text/hello.txt"), 0, "upper"),
+                source_modified,
+            };
+            asset_server.set_processed_asset_io(
+                MemoryAssetIo::new()
+                    .with_asset(&processed.output_path, "HELLO")
+                    .with_asset(
+                        ProcessedMeta::meta_path(Path::new("text/hello.txt")),
+                        ron::ser::to_string(&processed).unwrap(),
+                    ),
+            );
+        };
+
+        let asset_path = AssetPath::from("text/hello.txt");
+        set_processed_meta(source.modified(Path::new("text/hello.txt")));
+        let (_, bytes) =
+            future::block_on(asset_server.load_processed(&asset_path, &source)).unwrap();
+        assert_eq!(bytes, b"HELLO");
+
+        set_processed_meta(None);
+        assert!(future::block_on(asset_server.load_processed(&asset_path, &source)).is_none());
+        std::fs::remove_dir_all(source_folder).unwrap();
+    }
+}
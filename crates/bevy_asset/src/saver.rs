@@ -0,0 +1,61 @@
+use crate::{Asset, AssetDynamic};
+use anyhow::Result;
+use std::path::Path;
+
+/// Serializes assets of type [AssetSaver::Asset] so that they can be written back to an asset source.
+///
+/// Savers are usually paired with an [AssetLoader](crate::AssetLoader) for the same extensions, so that saved assets
+/// can be loaded again.
+pub trait AssetSaver: Send + Sync + 'static {
+    type Asset: Asset;
+
+    fn save(&self, asset: &Self::Asset, path: &Path) -> Result<Vec<u8>, anyhow::Error>;
+
+    /// Returns a function that produces the bytes of the asset, which the [AssetServer](crate::AssetServer) runs on
+    /// its task pool before writing them. By default the asset is serialized right away with [AssetSaver::save].
+    /// Savers of large assets can override this to copy the asset and serialize the copy on the task pool instead.
+    fn save_deferred(
+        &self,
+        asset: &Self::Asset,
+        path: &Path,
+    ) -> Result<DeferredSave, anyhow::Error> {
+        let bytes = self.save(asset, path)?;
+        Ok(Box::new(move || Ok(bytes)))
+    }
+
+    /// The extensions of the files this saver writes
+    fn extensions(&self) -> &[&str];
+}
+
+/// Produces the bytes of an asset that is being saved. See [AssetSaver::save_deferred].
+pub type DeferredSave = Box<dyn FnOnce() -> Result<Vec<u8>, anyhow::Error> + Send>;
+
+/// A type erased [AssetSaver], so that savers for different asset types can be stored together
+pub(crate) trait AssetSaverDynamic: Send + Sync + 'static {
+    fn save_dynamic(
+        &self,
+        asset: &dyn AssetDynamic,
+        path: &Path,
+    ) -> Result<DeferredSave, anyhow::Error>;
+    fn extensions(&self) -> &[&str];
+}
+
+impl<T: AssetSaver> AssetSaverDynamic for T {
+    fn save_dynamic(
+        &self,
+        asset: &dyn AssetDynamic,
+        path: &Path,
+    ) -> Result<DeferredSave, anyhow::Error> {
+        let asset = asset.downcast_ref::<T::Asset>().unwrap_or_else(|| {
+            panic!(
+                "failed to downcast asset to {}",
+                std::any::type_name::<T::Asset>()
+            )
+        });
+        self.save_deferred(asset, path)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        AssetSaver::extensions(self)
+    }
+}
@@ -16,5 +16,10 @@ pub fn derive_dynamic_plugin(input: TokenStream) -> TokenStream {
             let boxed = Box::new(object);
             Box::into_raw(boxed)
         }
+
+        #[no_mangle]
+        pub extern "C" fn _bevy_plugin_abi_version() -> *const std::os::raw::c_char {
+            bevy::app::DYNAMIC_PLUGIN_ABI_VERSION.as_ptr() as *const std::os::raw::c_char
+        }
     })
 }
@@ -1,27 +1,370 @@
 use libloading::{Library, Symbol};
+use std::{
+    ffi::CStr,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
 
-use bevy_app::{AppBuilder, CreatePlugin, Plugin};
+use bevy_app::{
+    App, AppBuilder, CreatePlugin, GetPluginAbiVersion, Plugin, DYNAMIC_PLUGIN_ABI_VERSION,
+};
+use bevy_ecs::SystemId;
+use bevy_utils::HashMap;
 
-/// Dynamically links a plugin a the given path. The plugin must export the [CreatePlugin] function.
-pub fn dynamically_load_plugin(path: &str) -> (Library, Box<dyn Plugin>) {
-    let lib = Library::new(path).unwrap();
+/// Errors that occur while loading, unloading, or reloading a dynamic plugin
+#[derive(Error, Debug)]
+pub enum DynamicPluginError {
+    #[error("failed to load dynamic library {0:?}")]
+    LibraryLoad(PathBuf, #[source] libloading::Error),
+    #[error(
+        "dynamic library {0:?} does not export the `{1}` symbol. Plugins must derive DynamicPlugin"
+    )]
+    MissingSymbol(PathBuf, &'static str, #[source] libloading::Error),
+    #[error("dynamic library {path:?} was built for plugin ABI `{found}`, but this app uses `{expected}`")]
+    AbiMismatch {
+        path: PathBuf,
+        expected: String,
+        found: String,
+    },
+    #[error("no dynamic plugin with id {0:?} is loaded")]
+    NotLoaded(DynamicPluginId),
+}
+
+/// Returns the plugin ABI version string of the running app, without its nul terminator.
+pub fn dynamic_plugin_abi_version() -> &'static str {
+    DYNAMIC_PLUGIN_ABI_VERSION.trim_end_matches('\0')
+}
+
+/// Returns an error if a plugin built for the ABI version `found` can't be used by the running app
+fn check_abi_version(path: &Path, found: &str) -> Result<(), DynamicPluginError> {
+    let expected = dynamic_plugin_abi_version();
+    if found != expected {
+        return Err(DynamicPluginError::AbiMismatch {
+            path: path.to_owned(),
+            expected: expected.to_string(),
+            found: found.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Dynamically links a plugin at the given path. The plugin must export the [CreatePlugin] and [GetPluginAbiVersion]
+/// functions, which are generated by `#[derive(DynamicPlugin)]`.
+///
+/// The plugin is only created if it was built with the same Bevy version and compiler as the running app. The returned
+/// [Library] must outlive the plugin and anything the plugin registered, such as systems.
+pub fn dynamically_load_plugin<P: AsRef<Path>>(
+    path: P,
+) -> Result<(Library, Box<dyn Plugin>), DynamicPluginError> {
+    let path = path.as_ref();
+    let lib =
+        Library::new(path).map_err(|err| DynamicPluginError::LibraryLoad(path.to_owned(), err))?;
 
     unsafe {
-        let func: Symbol<CreatePlugin> = lib.get(b"_create_plugin").unwrap();
+        let abi_version: Symbol<GetPluginAbiVersion> =
+            lib.get(b"_bevy_plugin_abi_version").map_err(|err| {
+                DynamicPluginError::MissingSymbol(path.to_owned(), "_bevy_plugin_abi_version", err)
+            })?;
+        check_abi_version(path, &CStr::from_ptr(abi_version()).to_string_lossy())?;
+
+        let func: Symbol<CreatePlugin> = lib.get(b"_create_plugin").map_err(|err| {
+            DynamicPluginError::MissingSymbol(path.to_owned(), "_create_plugin", err)
+        })?;
         let plugin = Box::from_raw(func());
-        (lib, plugin)
+        Ok((lib, plugin))
+    }
+}
+
+/// Identifies a plugin loaded with [DynamicPluginExt::try_load_plugin]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DynamicPluginId(pub usize);
+
+struct LoadedDynamicPlugin {
+    path: PathBuf,
+    systems: Vec<SystemId>,
+    startup_systems: Vec<SystemId>,
+    // NOTE: the plugin must be dropped before the library that contains its code
+    plugin: Option<Box<dyn Plugin>>,
+    library: Option<Library>,
+}
+
+impl LoadedDynamicPlugin {
+    fn close(mut self) {
+        self.plugin.take();
+        if let Some(library) = self.library.take() {
+            if let Err(err) = library.close() {
+                log::warn!("failed to close dynamic library {:?}: {}", self.path, err);
+            }
+        }
+    }
+}
+
+impl Drop for LoadedDynamicPlugin {
+    fn drop(&mut self) {
+        // The app's schedules may still hold systems that point into the library, so libraries that were not
+        // explicitly unloaded are kept in memory for the rest of the process.
+        if let Some(plugin) = self.plugin.take() {
+            std::mem::forget(plugin);
+        }
+        if let Some(library) = self.library.take() {
+            std::mem::forget(library);
+        }
+    }
+}
+
+/// Keeps the libraries of dynamically loaded plugins alive, along with the systems each plugin added
+#[derive(Default)]
+pub struct DynamicPlugins {
+    plugins: HashMap<DynamicPluginId, LoadedDynamicPlugin>,
+    next_id: usize,
+}
+
+impl DynamicPlugins {
+    pub fn path(&self, id: DynamicPluginId) -> Option<&Path> {
+        self.plugins.get(&id).map(|plugin| plugin.path.as_path())
+    }
+
+    pub fn name(&self, id: DynamicPluginId) -> Option<&str> {
+        self.plugins
+            .get(&id)
+            .and_then(|plugin| plugin.plugin.as_ref())
+            .map(|plugin| plugin.name())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (DynamicPluginId, &Path)> {
+        self.plugins
+            .iter()
+            .map(|(id, plugin)| (*id, plugin.path.as_path()))
+    }
+
+    fn next_id(&mut self) -> DynamicPluginId {
+        let id = DynamicPluginId(self.next_id);
+        self.next_id += 1;
+        id
     }
 }
 
 pub trait DynamicPluginExt {
+    /// Loads and builds the plugin at the given path. Panics if the plugin cannot be loaded.
     fn load_plugin(&mut self, path: &str) -> &mut Self;
+
+    /// Loads and builds the plugin at the given path, keeping its [Library] alive in the [DynamicPlugins] resource
+    fn try_load_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<DynamicPluginId, DynamicPluginError>;
+
+    /// Removes the systems the plugin added, then unloads its library. Resources and entities created by the plugin
+    /// are left in place, so they must not contain types whose code lives in the plugin.
+    fn unload_plugin(&mut self, id: DynamicPluginId) -> Result<(), DynamicPluginError>;
+
+    /// Unloads the plugin, then loads and builds it again from the same path under the same [DynamicPluginId]
+    fn reload_plugin(&mut self, id: DynamicPluginId) -> Result<(), DynamicPluginError>;
 }
 
 impl DynamicPluginExt for AppBuilder {
     fn load_plugin(&mut self, path: &str) -> &mut Self {
-        let (_lib, plugin) = dynamically_load_plugin(path);
-        log::debug!("loaded plugin: {}", plugin.name());
-        plugin.build(self);
+        if let Err(err) = self.try_load_plugin(path) {
+            panic!("{}", err);
+        }
         self
     }
+
+    fn try_load_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<DynamicPluginId, DynamicPluginError> {
+        let id = self
+            .resources_mut()
+            .get_or_insert_with(DynamicPlugins::default)
+            .next_id();
+        build_plugin(self, id, path.as_ref())?;
+        Ok(id)
+    }
+
+    fn unload_plugin(&mut self, id: DynamicPluginId) -> Result<(), DynamicPluginError> {
+        let loaded = self
+            .resources()
+            .get_mut::<DynamicPlugins>()
+            .and_then(|mut plugins| plugins.plugins.remove(&id))
+            .ok_or(DynamicPluginError::NotLoaded(id))?;
+
+        for system_id in loaded.systems.iter() {
+            self.app.schedule.remove_system(*system_id);
+        }
+        for system_id in loaded.startup_systems.iter() {
+            self.app.startup_schedule.remove_system(*system_id);
+        }
+
+        log::debug!("unloaded plugin: {:?}", loaded.path);
+        loaded.close();
+        Ok(())
+    }
+
+    fn reload_plugin(&mut self, id: DynamicPluginId) -> Result<(), DynamicPluginError> {
+        let path = self
+            .resources()
+            .get::<DynamicPlugins>()
+            .and_then(|plugins| plugins.path(id).map(|path| path.to_owned()))
+            .ok_or(DynamicPluginError::NotLoaded(id))?;
+        self.unload_plugin(id)?;
+        build_plugin(self, id, &path)
+    }
+}
+
+/// Allows plugins to be unloaded and reloaded while the [App] runs, for example from a custom runner.
+/// Startup systems added by a reloaded plugin are not run again.
+impl DynamicPluginExt for App {
+    fn load_plugin(&mut self, path: &str) -> &mut Self {
+        with_app_builder(self, |app_builder| {
+            app_builder.load_plugin(path);
+        });
+        self
+    }
+
+    fn try_load_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<DynamicPluginId, DynamicPluginError> {
+        with_app_builder(self, |app_builder| app_builder.try_load_plugin(path))
+    }
+
+    fn unload_plugin(&mut self, id: DynamicPluginId) -> Result<(), DynamicPluginError> {
+        with_app_builder(self, |app_builder| app_builder.unload_plugin(id))
+    }
+
+    fn reload_plugin(&mut self, id: DynamicPluginId) -> Result<(), DynamicPluginError> {
+        with_app_builder(self, |app_builder| app_builder.reload_plugin(id))
+    }
+}
+
+fn with_app_builder<T>(app: &mut App, func: impl FnOnce(&mut AppBuilder) -> T) -> T {
+    let mut app_builder = AppBuilder {
+        app: std::mem::take(app),
+    };
+    let result = func(&mut app_builder);
+    *app = app_builder.app;
+    result
+}
+
+fn build_plugin(
+    app_builder: &mut AppBuilder,
+    id: DynamicPluginId,
+    path: &Path,
+) -> Result<(), DynamicPluginError> {
+    let (library, plugin) = dynamically_load_plugin(path)?;
+    log::debug!("loaded plugin: {}", plugin.name());
+
+    let schedule_systems = app_builder.app.schedule.system_ids().collect::<Vec<_>>();
+    let startup_systems = app_builder
+        .app
+        .startup_schedule
+        .system_ids()
+        .collect::<Vec<_>>();
+
+    plugin.build(app_builder);
+
+    let loaded = LoadedDynamicPlugin {
+        path: path.to_owned(),
+        systems: app_builder
+            .app
+            .schedule
+            .system_ids()
+            .filter(|id| !schedule_systems.contains(id))
+            .collect(),
+        startup_systems: app_builder
+            .app
+            .startup_schedule
+            .system_ids()
+            .filter(|id| !startup_systems.contains(id))
+            .collect(),
+        plugin: Some(plugin),
+        library: Some(library),
+    };
+
+    app_builder
+        .resources_mut()
+        .get_or_insert_with(DynamicPlugins::default)
+        .plugins
+        .insert(id, loaded);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_abi_version, dynamic_plugin_abi_version, DynamicPluginError, DynamicPluginExt,
+        DynamicPluginId, DynamicPlugins, LoadedDynamicPlugin,
+    };
+    use bevy_app::App;
+    use bevy_ecs::{IntoQuerySystem, ResMut};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn plugins_with_another_abi_are_rejected() {
+        let path = Path::new("plugins/libgame.so");
+        assert!(check_abi_version(path, dynamic_plugin_abi_version()).is_ok());
+        match check_abi_version(path, "bevy 0.1.0 rustc 1.40.0") {
+            Err(DynamicPluginError::AbiMismatch {
+                path,
+                expected,
+                found,
+            }) => {
+                assert_eq!(path, PathBuf::from("plugins/libgame.so"));
+                assert_eq!(expected, dynamic_plugin_abi_version());
+                assert_eq!(found, "bevy 0.1.0 rustc 1.40.0");
+            }
+            result => panic!("expected an ABI mismatch, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn missing_libraries_fail_to_load() {
+        let mut app_builder = App::build();
+        let result = app_builder.try_load_plugin("plugins/does_not_exist.so");
+        assert!(matches!(result, Err(DynamicPluginError::LibraryLoad(..))));
+        assert!(matches!(
+            app_builder.unload_plugin(DynamicPluginId(0)),
+            Err(DynamicPluginError::NotLoaded(DynamicPluginId(0)))
+        ));
+    }
+
+    fn plugin_system(mut count: ResMut<u32>) {
+        *count += 1;
+    }
+
+    #[test]
+    fn unloading_removes_plugin_systems() {
+        let mut app_builder = App::build();
+        app_builder
+            .add_resource(0u32)
+            .add_system(plugin_system.system());
+        let system_ids = app_builder.app.schedule.system_ids().collect::<Vec<_>>();
+        let mut plugins = DynamicPlugins::default();
+        let id = plugins.next_id();
+        plugins.plugins.insert(
+            id,
+            LoadedDynamicPlugin {
+                path: PathBuf::from("plugins/libgame.so"),
+                systems: system_ids,
+                startup_systems: Vec::new(),
+                plugin: None,
+                library: None,
+            },
+        );
+        app_builder.add_resource(plugins);
+
+        app_builder.unload_plugin(id).unwrap();
+        assert_eq!(app_builder.app.schedule.system_ids().count(), 0);
+        assert!(app_builder
+            .resources()
+            .get::<DynamicPlugins>()
+            .unwrap()
+            .path(id)
+            .is_none());
+        assert!(matches!(
+            app_builder.reload_plugin(id),
+            Err(DynamicPluginError::NotLoaded(_))
+        ));
+    }
 }
@@ -144,6 +144,29 @@ impl Schedule {
         self
     }
 
+    /// Removes the system with the given id from whichever stage contains it, returning the removed system.
+    pub fn remove_system(&mut self, system_id: SystemId) -> Option<Box<dyn System>> {
+        if !self.system_ids.remove(&system_id) {
+            return None;
+        }
+
+        for stage_systems in self.stages.values_mut() {
+            if let Some(index) = stage_systems
+                .iter()
+                .position(|system| system.id() == system_id)
+            {
+                self.generation += 1;
+                return Some(stage_systems.remove(index));
+            }
+        }
+
+        None
+    }
+
+    pub fn system_ids(&self) -> impl Iterator<Item = SystemId> + '_ {
+        self.system_ids.iter().cloned()
+    }
+
     pub fn run(&mut self, world: &mut World, resources: &mut Resources) {
         for stage_name in self.stage_order.iter() {
             if let Some(stage_systems) = self.stages.get_mut(stage_name) {
@@ -199,3 +222,42 @@ impl Schedule {
         self.generation
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Schedule;
+    use crate::{
+        resource::{ResMut, Resources},
+        system::IntoQuerySystem,
+    };
+    use bevy_hecs::World;
+
+    fn increment(mut count: ResMut<u32>) {
+        *count += 1;
+    }
+
+    #[test]
+    fn removed_systems_no_longer_run() {
+        let mut world = World::new();
+        let mut resources = Resources::default();
+        resources.insert(0u32);
+
+        let mut schedule = Schedule::default();
+        schedule.add_stage("update");
+        let system = increment.system();
+        let system_id = system.id();
+        schedule.add_system_to_stage("update", system);
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<u32>().unwrap(), 1);
+
+        let generation = schedule.generation();
+        assert!(schedule.remove_system(system_id).is_some());
+        assert!(schedule.generation() > generation);
+        assert_eq!(schedule.system_ids().count(), 0);
+        assert!(schedule.remove_system(system_id).is_none());
+        schedule.initialize(&mut world, &mut resources);
+        schedule.run(&mut world, &mut resources);
+        assert_eq!(*resources.get::<u32>().unwrap(), 1);
+    }
+}
@@ -1,5 +1,7 @@
 use anyhow::Result;
-use bevy_asset::{AssetIoError, AssetLoader, AssetPath, LoadContext, LoadedAsset};
+use bevy_asset::{
+    deserialize_settings, AssetIoError, AssetLoader, LoadContext, LoadedAsset, LoaderSettings,
+};
 use bevy_ecs::{bevy_utils::BoxedFuture, World, WorldBuilderSource};
 use bevy_math::Mat4;
 use bevy_pbr::prelude::{PbrComponents, StandardMaterial};
@@ -16,6 +18,7 @@ use bevy_transform::{
 };
 use gltf::{mesh::Mode, Primitive};
 use image::{GenericImageView, ImageFormat};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use thiserror::Error;
 
@@ -42,6 +45,23 @@ pub enum GltfError {
     AssetIoError(#[from] AssetIoError),
 }
 
+/// Settings for [GltfLoader], read from a `.gltf.meta` or `.glb.meta` file or passed to
+/// [AssetServer::load_with_settings](bevy_asset::AssetServer::load_with_settings)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GltfLoaderSettings {
+    /// If false, materials are loaded without textures
+    pub load_textures: bool,
+}
+
+impl Default for GltfLoaderSettings {
+    fn default() -> Self {
+        Self {
+            load_textures: true,
+        }
+    }
+}
+
 /// Loads meshes from GLTF files into Mesh assets
 #[derive(Default)]
 pub struct GltfLoader;
@@ -59,12 +79,23 @@ impl AssetLoader for GltfLoader {
         static EXTENSIONS: &[&str] = &["gltf", "glb"];
         EXTENSIONS
     }
+
+    fn deserialize_settings(
+        &self,
+        settings: &str,
+    ) -> Result<Box<dyn LoaderSettings>, anyhow::Error> {
+        deserialize_settings::<GltfLoaderSettings>(settings)
+    }
 }
 
 async fn load_gltf<'a, 'b>(
     bytes: &'a [u8],
     load_context: &'a mut LoadContext<'b>,
 ) -> Result<(), GltfError> {
+    let settings = load_context
+        .settings::<GltfLoaderSettings>()
+        .cloned()
+        .unwrap_or_default();
     let gltf = gltf::Gltf::from_slice(bytes)?;
     let mut world = World::default();
     let buffer_data = load_buffers(&gltf, load_context, load_context.path()).await?;
@@ -110,7 +141,7 @@ async fn load_gltf<'a, 'b>(
         }
     }
 
-    for texture in gltf.textures() {
+    for texture in gltf.textures().filter(|_| settings.load_textures) {
         if let gltf::image::Source::View { view, mime_type } = texture.source().source() {
             let start = view.offset() as usize;
             let end = (view.offset() + view.length()) as usize;
@@ -142,17 +173,18 @@ async fn load_gltf<'a, 'b>(
         let material_label = material_label(&material);
         let pbr = material.pbr_metallic_roughness();
         let mut dependencies = Vec::new();
-        let texture_handle = if let Some(info) = pbr.base_color_texture() {
+        let texture_info = pbr.base_color_texture().filter(|_| settings.load_textures);
+        let texture_handle = if let Some(info) = texture_info {
             match info.texture().source().source() {
                 gltf::image::Source::View { .. } => {
                     let label = texture_label(&info.texture());
-                    let path = AssetPath::new_ref(load_context.path(), Some(&label));
+                    let path = load_context.labeled_asset_path(&label);
                     Some(load_context.get_handle(path))
                 }
                 gltf::image::Source::Uri { uri, .. } => {
                     let parent = load_context.path().parent().unwrap();
                     let image_path = parent.join(uri);
-                    let asset_path = AssetPath::new(image_path, None);
+                    let asset_path = load_context.get_asset_path(image_path, None);
                     let handle = load_context.get_handle(asset_path.clone());
                     dependencies.push(asset_path);
                     Some(handle)
@@ -213,12 +245,10 @@ fn load_node(
             if let Some(mesh) = node.mesh() {
                 for primitive in mesh.primitives() {
                     let primitive_label = primitive_label(&mesh, &primitive);
-                    let mesh_asset_path =
-                        AssetPath::new_ref(load_context.path(), Some(&primitive_label));
+                    let mesh_asset_path = load_context.labeled_asset_path(&primitive_label);
                     let material = primitive.material();
                     let material_label = material_label(&material);
-                    let material_asset_path =
-                        AssetPath::new_ref(load_context.path(), Some(&material_label));
+                    let material_asset_path = load_context.labeled_asset_path(&material_label);
                     parent.spawn(PbrComponents {
                         mesh: load_context.get_handle(mesh_asset_path),
                         material: load_context.get_handle(material_asset_path),
@@ -5,22 +5,141 @@ mod modules;
 use modules::{get_modules, get_path};
 use proc_macro::TokenStream;
 use proc_macro_crate::crate_name;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     parse::{Parse, ParseStream},
     parse_macro_input,
     punctuated::Punctuated,
     token::{Comma, Where},
-    Data, DataStruct, DeriveInput, Field, Fields, Generics, Ident, Index, Member,
+    Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, Generics, Ident, Index, Lit,
+    LitStr, Member, Meta, MetaNameValue, Token,
 };
 
 #[derive(Default)]
 struct PropAttributeArgs {
     pub ignore: Option<bool>,
+    pub default: Option<Expr>,
+    pub range: Option<(Expr, Expr)>,
+    pub step: Option<Expr>,
+    pub rename: Option<String>,
+    pub tooltip: Option<String>,
+    /// The names of the arguments that set [FieldAttributes] other than the name, such as `default` and `range`
+    pub field_attribute_idents: Vec<Ident>,
 }
 
 static PROP_ATTRIBUTE_NAME: &str = "property";
 
+fn get_attribute_args(field: &Field) -> PropAttributeArgs {
+    let mut attribute_args = PropAttributeArgs::default();
+    let attribute = field
+        .attrs
+        .iter()
+        .find(|a| *a.path.get_ident().as_ref().unwrap() == PROP_ATTRIBUTE_NAME);
+    if let Some(attribute) = attribute {
+        attribute
+            .parse_args_with(|input: ParseStream| {
+                while !input.is_empty() {
+                    let name = input.parse::<Ident>()?;
+                    if name == "ignore" {
+                        attribute_args.ignore = Some(true);
+                    } else {
+                        input.parse::<Token![=]>()?;
+                        if name != "rename" {
+                            attribute_args.field_attribute_idents.push(name.clone());
+                        }
+                        match name.to_string().as_str() {
+                            "default" => attribute_args.default = Some(input.parse()?),
+                            "range" => {
+                                let from = input.parse()?;
+                                input.parse::<Token![..=]>()?;
+                                attribute_args.range = Some((from, input.parse()?));
+                            }
+                            "step" => attribute_args.step = Some(input.parse()?),
+                            "rename" => {
+                                attribute_args.rename = Some(input.parse::<LitStr>()?.value())
+                            }
+                            "tooltip" => {
+                                attribute_args.tooltip = Some(input.parse::<LitStr>()?.value())
+                            }
+                            _ => {
+                                return Err(syn::Error::new(
+                                    name.span(),
+                                    "unknown property attribute",
+                                ))
+                            }
+                        }
+                    }
+                    if !input.is_empty() {
+                        input.parse::<Comma>()?;
+                    }
+                }
+                Ok(())
+            })
+            .expect("invalid 'property' attribute format");
+    }
+
+    // doc comments are used as tooltips unless a tooltip is set explicitly
+    if attribute_args.tooltip.is_none() {
+        let doc_lines = field
+            .attrs
+            .iter()
+            .filter(|a| a.path.is_ident("doc"))
+            .filter_map(|a| match a.parse_meta() {
+                Ok(Meta::NameValue(MetaNameValue {
+                    lit: Lit::Str(doc), ..
+                })) => Some(doc.value().trim().to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !doc_lines.is_empty() {
+            attribute_args.tooltip = Some(doc_lines.join("\n"));
+        }
+    }
+
+    attribute_args
+}
+
+/// A field that is not ignored, with its parsed `#[property(...)]` attribute
+struct ActiveField<'a> {
+    field: &'a Field,
+    index: usize,
+    args: PropAttributeArgs,
+}
+
+impl<'a> ActiveField<'a> {
+    /// The name the field is accessed and serialized with
+    fn name(&self) -> String {
+        self.args.rename.clone().unwrap_or_else(|| {
+            self.field
+                .ident
+                .as_ref()
+                .map(|i| i.to_string())
+                .unwrap_or_else(|| self.index.to_string())
+        })
+    }
+
+    /// The name the field is bound to in enum variant patterns
+    fn binding(&self) -> Ident {
+        self.field
+            .ident
+            .clone()
+            .unwrap_or_else(|| format_ident!("field_{}", self.index))
+    }
+}
+
+fn get_active_fields(fields: &Punctuated<Field, Comma>) -> Vec<ActiveField<'_>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| ActiveField {
+            field,
+            index,
+            args: get_attribute_args(field),
+        })
+        .filter(|active_field| !active_field.args.ignore.unwrap_or(false))
+        .collect()
+}
+
 #[proc_macro_derive(Properties, attributes(property, module))]
 pub fn derive_properties(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -38,68 +157,27 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
             fields: Fields::Unit,
             ..
         }) => &unit_struct_punctuated,
-        _ => panic!("expected a struct with named fields"),
+        Data::Enum(data_enum) => return derive_enum_properties(&ast, data_enum),
+        _ => panic!("expected a struct or an enum"),
     };
-    let fields_and_args = fields
-        .iter()
-        .enumerate()
-        .map(|(i, f)| {
-            (
-                f,
-                f.attrs
-                    .iter()
-                    .find(|a| *a.path.get_ident().as_ref().unwrap() == PROP_ATTRIBUTE_NAME)
-                    .map(|a| {
-                        syn::custom_keyword!(ignore);
-                        let mut attribute_args = PropAttributeArgs { ignore: None };
-                        a.parse_args_with(|input: ParseStream| {
-                            if input.parse::<Option<ignore>>()?.is_some() {
-                                attribute_args.ignore = Some(true);
-                                return Ok(());
-                            }
-                            Ok(())
-                        })
-                        .expect("invalid 'property' attribute format");
-
-                        attribute_args
-                    }),
-                i,
-            )
-        })
-        .collect::<Vec<(&Field, Option<PropAttributeArgs>, usize)>>();
-    let active_fields = fields_and_args
-        .iter()
-        .filter(|(_field, attrs, _i)| {
-            attrs.is_none()
-                || match attrs.as_ref().unwrap().ignore {
-                    Some(ignore) => !ignore,
-                    None => true,
-                }
-        })
-        .map(|(f, _attr, i)| (*f, *i))
-        .collect::<Vec<(&Field, usize)>>();
+    let active_fields = get_active_fields(fields);
 
     let modules = get_modules();
     let bevy_property_path = get_path(&modules.bevy_property);
 
     let field_names = active_fields
         .iter()
-        .map(|(field, index)| {
-            field
-                .ident
-                .as_ref()
-                .map(|i| i.to_string())
-                .unwrap_or_else(|| index.to_string())
-        })
+        .map(ActiveField::name)
         .collect::<Vec<String>>();
     let field_idents = active_fields
         .iter()
-        .map(|(field, index)| {
-            field
+        .map(|active_field| {
+            active_field
+                .field
                 .ident
                 .as_ref()
                 .map(|ident| Member::Named(ident.clone()))
-                .unwrap_or_else(|| Member::Unnamed(Index::from(*index)))
+                .unwrap_or_else(|| Member::Unnamed(Index::from(active_field.index)))
         })
         .collect::<Vec<_>>();
     let field_count = active_fields.len();
@@ -110,6 +188,45 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
 
     let struct_name = &ast.ident;
 
+    let field_attributes = active_fields
+        .iter()
+        .zip(field_names.iter())
+        .map(|(active_field, name)| {
+            let args = &active_field.args;
+            let ty = &active_field.field.ty;
+            let default = match &args.default {
+                Some(default) => quote! {
+                    Some((|| {
+                        let value: #ty = #default;
+                        Box::new(value) as Box<dyn #bevy_property_path::Property>
+                    }) as fn() -> Box<dyn #bevy_property_path::Property>)
+                },
+                None => quote! { None },
+            };
+            let range = match &args.range {
+                Some((from, to)) => quote! { Some((#from as f64)..=(#to as f64)) },
+                None => quote! { None },
+            };
+            let step = match &args.step {
+                Some(step) => quote! { Some(#step as f64) },
+                None => quote! { None },
+            };
+            let tooltip = match &args.tooltip {
+                Some(tooltip) => quote! { Some(#tooltip) },
+                None => quote! { None },
+            };
+            quote! {
+                #bevy_property_path::FieldAttributes {
+                    name: #name,
+                    default: #default,
+                    range: #range,
+                    step: #step,
+                    tooltip: #tooltip,
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
     TokenStream::from(quote! {
         impl #impl_generics #bevy_property_path::Properties for #struct_name#ty_generics {
             fn prop(&self, name: &str) -> Option<&dyn #bevy_property_path::Property> {
@@ -166,6 +283,10 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
                     let dynamic_properties: #bevy_property_path::DynamicProperties = dynamic_properties_deserializer.deserialize(deserializer)?;
                     Ok(Box::new(dynamic_properties))
             }
+
+            fn field_attributes() -> Vec<#bevy_property_path::FieldAttributes> {
+                vec![#(#field_attributes,)*]
+            }
         }
 
         impl #impl_generics #bevy_property_path::Property for #struct_name#ty_generics {
@@ -216,6 +337,11 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
                 Some(self)
             }
 
+            #[inline]
+            fn as_properties_mut(&mut self) -> Option<&mut dyn #bevy_property_path::Properties> {
+                Some(self)
+            }
+
             fn serializable<'a>(&'a self, registry: &'a #bevy_property_path::PropertyTypeRegistry) -> #bevy_property_path::property_serde::Serializable<'a> {
                 #bevy_property_path::property_serde::Serializable::Owned(Box::new(#bevy_property_path::property_serde::MapSerializer::new(self, registry)))
             }
@@ -227,6 +353,322 @@ pub fn derive_properties(input: TokenStream) -> TokenStream {
     })
 }
 
+struct VariantInfo<'a> {
+    ident: &'a Ident,
+    fields: &'a Fields,
+    active_fields: Vec<ActiveField<'a>>,
+}
+
+impl<'a> VariantInfo<'a> {
+    /// A pattern that binds the active fields of this variant
+    fn pattern(&self, enum_name: &Ident) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        match self.fields {
+            Fields::Named(_) => {
+                let bindings = self.active_fields.iter().map(ActiveField::binding);
+                quote! { #enum_name::#ident { #(#bindings,)* .. } }
+            }
+            Fields::Unnamed(fields) => {
+                let bindings = (0..fields.unnamed.len()).map(|index| {
+                    if self
+                        .active_fields
+                        .iter()
+                        .any(|active_field| active_field.index == index)
+                    {
+                        let binding = format_ident!("field_{}", index);
+                        quote! { #binding }
+                    } else {
+                        quote! { _ }
+                    }
+                });
+                quote! { #enum_name::#ident(#(#bindings,)*) }
+            }
+            Fields::Unit => quote! { #enum_name::#ident },
+        }
+    }
+
+    /// A pattern that matches this variant without binding its fields
+    fn empty_pattern(&self, enum_name: &Ident) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        match self.fields {
+            Fields::Named(_) => quote! { #enum_name::#ident { .. } },
+            Fields::Unnamed(_) => quote! { #enum_name::#ident(..) },
+            Fields::Unit => quote! { #enum_name::#ident },
+        }
+    }
+
+    /// Constructs this variant with default field values
+    fn default_value(&self, enum_name: &Ident) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        match self.fields {
+            Fields::Named(fields) => {
+                let names = fields
+                    .named
+                    .iter()
+                    .map(|field| field.ident.as_ref().unwrap());
+                quote! { #enum_name::#ident { #(#names: Default::default(),)* } }
+            }
+            Fields::Unnamed(fields) => {
+                let defaults = fields.unnamed.iter().map(|_| quote! { Default::default() });
+                quote! { #enum_name::#ident(#(#defaults,)*) }
+            }
+            Fields::Unit => quote! { #enum_name::#ident },
+        }
+    }
+}
+
+fn derive_enum_properties(ast: &DeriveInput, data_enum: &DataEnum) -> TokenStream {
+    if data_enum.variants.is_empty() {
+        panic!("expected an enum with at least one variant");
+    }
+
+    let modules = get_modules();
+    let bevy_property_path = get_path(&modules.bevy_property);
+    let enum_name = &ast.ident;
+    let variants = data_enum
+        .variants
+        .iter()
+        .map(|variant| VariantInfo {
+            ident: &variant.ident,
+            fields: &variant.fields,
+            active_fields: match &variant.fields {
+                Fields::Named(fields) => get_active_fields(&fields.named),
+                Fields::Unnamed(fields) => get_active_fields(&fields.unnamed),
+                Fields::Unit => Vec::new(),
+            },
+        })
+        .collect::<Vec<_>>();
+
+    // field attributes are looked up by name, and fields of different variants can have the same name
+    if let Some(ident) = variants
+        .iter()
+        .flat_map(|variant| variant.active_fields.iter())
+        .find_map(|active_field| active_field.args.field_attribute_idents.first())
+    {
+        return TokenStream::from(
+            syn::Error::new(
+                ident.span(),
+                "only `rename` and `ignore` are supported on the fields of enum variants",
+            )
+            .to_compile_error(),
+        );
+    }
+
+    // struct variant fields can be accessed by name. tuple variant fields can only be accessed by index
+    let name_arms = variants
+        .iter()
+        .map(|variant| match variant.fields {
+            Fields::Named(_) if !variant.active_fields.is_empty() => {
+                let pattern = variant.pattern(enum_name);
+                let names = variant.active_fields.iter().map(ActiveField::name);
+                let bindings = variant.active_fields.iter().map(ActiveField::binding);
+                quote! {
+                    #pattern => match name {
+                        #(#names => Some(#bindings),)*
+                        _ => None,
+                    }
+                }
+            }
+            _ => {
+                let pattern = variant.empty_pattern(enum_name);
+                quote! { #pattern => None }
+            }
+        })
+        .collect::<Vec<_>>();
+    let index_arms = variants
+        .iter()
+        .map(|variant| {
+            if variant.active_fields.is_empty() {
+                let pattern = variant.empty_pattern(enum_name);
+                quote! { #pattern => None }
+            } else {
+                let pattern = variant.pattern(enum_name);
+                let indices = 0..variant.active_fields.len();
+                let bindings = variant.active_fields.iter().map(ActiveField::binding);
+                quote! {
+                    #pattern => match index {
+                        #(#indices => Some(#bindings),)*
+                        _ => None,
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let prop_name_arms = variants.iter().map(|variant| {
+        let pattern = variant.empty_pattern(enum_name);
+        match variant.fields {
+            Fields::Named(_) if !variant.active_fields.is_empty() => {
+                let indices = 0..variant.active_fields.len();
+                let names = variant.active_fields.iter().map(ActiveField::name);
+                quote! {
+                    #pattern => match index {
+                        #(#indices => Some(#names),)*
+                        _ => None,
+                    }
+                }
+            }
+            _ => quote! { #pattern => None },
+        }
+    });
+    let prop_len_arms = variants.iter().map(|variant| {
+        let pattern = variant.empty_pattern(enum_name);
+        let len = variant.active_fields.len();
+        quote! { #pattern => #len }
+    });
+    let variant_name_arms = variants.iter().map(|variant| {
+        let pattern = variant.empty_pattern(enum_name);
+        let name = variant.ident.to_string();
+        quote! { #pattern => #name }
+    });
+    // switching to another variant starts from default field values, so the fields of derived enums must implement
+    // Default
+    let variant_default_arms = variants.iter().map(|variant| {
+        let name = variant.ident.to_string();
+        let value = variant.default_value(enum_name);
+        quote! { #name => #value }
+    });
+
+    let generics = &ast.generics;
+    let (impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        // `name` and `index` are unused when every variant is a unit variant
+        #[allow(unused_variables)]
+        impl #impl_generics #bevy_property_path::Properties for #enum_name#ty_generics {
+            fn prop(&self, name: &str) -> Option<&dyn #bevy_property_path::Property> {
+                match self {
+                    #(#name_arms,)*
+                }
+            }
+
+            fn prop_mut(&mut self, name: &str) -> Option<&mut dyn #bevy_property_path::Property> {
+                match self {
+                    #(#name_arms,)*
+                }
+            }
+
+            fn prop_with_index(&self, index: usize) -> Option<&dyn #bevy_property_path::Property> {
+                match self {
+                    #(#index_arms,)*
+                }
+            }
+
+            fn prop_with_index_mut(&mut self, index: usize) -> Option<&mut dyn #bevy_property_path::Property> {
+                match self {
+                    #(#index_arms,)*
+                }
+            }
+
+            fn prop_name(&self, index: usize) -> Option<&str> {
+                match self {
+                    #(#prop_name_arms,)*
+                }
+            }
+
+            fn prop_len(&self) -> usize {
+                match self {
+                    #(#prop_len_arms,)*
+                }
+            }
+
+            fn iter_props(&self) -> #bevy_property_path::PropertyIter {
+                #bevy_property_path::PropertyIter::new(self)
+            }
+
+            fn variant_name(&self) -> Option<&str> {
+                Some(match self {
+                    #(#variant_name_arms,)*
+                })
+            }
+        }
+
+        impl #impl_generics #bevy_property_path::DeserializeProperty for #enum_name#ty_generics {
+            fn deserialize(
+                deserializer: &mut dyn #bevy_property_path::erased_serde::Deserializer,
+                property_type_registry: &#bevy_property_path::PropertyTypeRegistry) ->
+                    Result<Box<dyn #bevy_property_path::Property>, #bevy_property_path::erased_serde::Error> {
+                    use #bevy_property_path::serde::de::DeserializeSeed;
+                    let dynamic_properties_deserializer = #bevy_property_path::property_serde::DynamicPropertiesDeserializer::new(property_type_registry);
+                    let dynamic_properties: #bevy_property_path::DynamicProperties = dynamic_properties_deserializer.deserialize(deserializer)?;
+                    Ok(Box::new(dynamic_properties))
+            }
+        }
+
+        impl #impl_generics #bevy_property_path::Property for #enum_name#ty_generics {
+            #[inline]
+            fn type_name(&self) -> &str {
+                std::any::type_name::<Self>()
+            }
+
+            #[inline]
+            fn any(&self) -> &dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn any_mut(&mut self) -> &mut dyn std::any::Any {
+                self
+            }
+            #[inline]
+            fn clone_prop(&self) -> Box<dyn #bevy_property_path::Property> {
+                Box::new(self.to_dynamic())
+            }
+            #[inline]
+            fn set(&mut self, value: &dyn #bevy_property_path::Property) {
+                self.apply(value);
+            }
+
+            fn apply(&mut self, value: &dyn #bevy_property_path::Property) {
+                use #bevy_property_path::Properties;
+                if let Some(properties) = value.as_properties() {
+                    if properties.property_type() != self.property_type() {
+                        panic!(
+                            "Properties type mismatch. This type is {:?} but the applied type is {:?}",
+                            self.property_type(),
+                            properties.property_type()
+                        );
+                    }
+                    let variant_name = properties.variant_name().expect("enums should have a variant name");
+                    if self.variant_name() != Some(variant_name) {
+                        *self = match variant_name {
+                            #(#variant_default_arms,)*
+                            _ => panic!("{} has no variant named {}", std::any::type_name::<Self>(), variant_name),
+                        };
+                    }
+                    for (i, prop) in properties.iter_props().enumerate() {
+                        let field = match properties.prop_name(i) {
+                            Some(name) => self.prop_mut(name),
+                            None => self.prop_with_index_mut(i),
+                        };
+                        if let Some(field) = field {
+                            field.apply(prop);
+                        }
+                    }
+                } else {
+                    panic!("attempted to apply non-Properties type to Properties type");
+                }
+            }
+
+            #[inline]
+            fn as_properties(&self) -> Option<&dyn #bevy_property_path::Properties> {
+                Some(self)
+            }
+
+            #[inline]
+            fn as_properties_mut(&mut self) -> Option<&mut dyn #bevy_property_path::Properties> {
+                Some(self)
+            }
+
+            fn serializable<'a>(&'a self, registry: &'a #bevy_property_path::PropertyTypeRegistry) -> #bevy_property_path::property_serde::Serializable<'a> {
+                #bevy_property_path::property_serde::Serializable::Owned(Box::new(#bevy_property_path::property_serde::EnumSerializer::new(self, registry)))
+            }
+
+            fn property_type(&self) -> #bevy_property_path::PropertyType {
+                #bevy_property_path::PropertyType::Enum
+            }
+        }
+    })
+}
+
 #[proc_macro_derive(Property)]
 pub fn derive_property(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
@@ -0,0 +1,24 @@
+use crate::{
+    property_serde::{BinaryPropertiesSerializer, DynamicPropertiesDeserializer},
+    DynamicProperties, Properties, PropertyTypeRegistry,
+};
+use bincode::Options;
+
+pub fn serialize_dynamic_properties(
+    properties: &dyn Properties,
+    property_type_registry: &PropertyTypeRegistry,
+) -> Result<Vec<u8>, bincode::Error> {
+    bincode::DefaultOptions::new().serialize(&BinaryPropertiesSerializer::new(
+        properties,
+        property_type_registry,
+    ))
+}
+
+pub fn deserialize_dynamic_properties(
+    bytes: &[u8],
+    property_type_registry: &PropertyTypeRegistry,
+) -> Result<DynamicProperties, bincode::Error> {
+    let dynamic_properties_deserializer =
+        DynamicPropertiesDeserializer::new(property_type_registry);
+    bincode::DefaultOptions::new().deserialize_seed(dynamic_properties_deserializer, bytes)
+}
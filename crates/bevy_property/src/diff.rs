@@ -0,0 +1,222 @@
+use crate::{
+    property_serde::DynamicPropertiesSerializer, DynamicProperties, Properties, Property,
+    PropertyType, PropertyTypeRegistry,
+};
+use bincode::Options;
+
+/// The changes between two values of the same type, created by [diff_properties]. The patch only contains the fields
+/// that changed, so it is useful for undo/redo and for sending changes over the network.
+#[derive(Debug)]
+pub struct PropertiesPatch {
+    pub properties: DynamicProperties,
+}
+
+impl PropertiesPatch {
+    /// Applies the changes to `target`. This is the same as applying [PropertiesPatch::properties] with
+    /// [Property::apply].
+    pub fn apply(&self, target: &mut dyn Property) {
+        target.apply(&self.properties);
+    }
+
+    pub fn serialize_ron(&self, registry: &PropertyTypeRegistry) -> Result<String, ron::Error> {
+        ron::ser::to_string(&DynamicPropertiesSerializer::new(
+            &self.properties,
+            registry,
+        ))
+    }
+
+    pub fn deserialize_ron(
+        ron_string: &str,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Self, ron::Error> {
+        let properties = crate::ron::deserialize_dynamic_properties(ron_string, registry)?;
+        Ok(PropertiesPatch { properties })
+    }
+
+    pub fn serialize_binary(
+        &self,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Vec<u8>, bincode::Error> {
+        crate::binary::serialize_dynamic_properties(&self.properties, registry)
+    }
+
+    pub fn deserialize_binary(
+        bytes: &[u8],
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Self, bincode::Error> {
+        let properties = crate::binary::deserialize_dynamic_properties(bytes, registry)?;
+        Ok(PropertiesPatch { properties })
+    }
+}
+
+/// Returns the changes that turn `old` into `new`, or `None` if they are equal.
+///
+/// Fields of maps and of struct-like enum variants are compared one by one, so the patch only contains the fields that
+/// changed. Sequences and tuple-like enum variants can't be patched partially, so they are included in full if any of
+/// their items changed. Values are compared by their serialized form.
+pub fn diff_properties(old: &dyn Properties, new: &dyn Properties) -> Option<PropertiesPatch> {
+    diff(old, new).map(|properties| PropertiesPatch { properties })
+}
+
+fn diff(old: &dyn Properties, new: &dyn Properties) -> Option<DynamicProperties> {
+    if old.type_name() != new.type_name()
+        || old.property_type() != new.property_type()
+        || old.variant_name() != new.variant_name()
+    {
+        return Some(new.to_dynamic());
+    }
+
+    let has_named_fields = new.prop_len() > 0 && new.prop_name(0).is_some();
+    let mut patch = match new.property_type() {
+        PropertyType::Map => DynamicProperties::map(),
+        PropertyType::Enum if has_named_fields => {
+            DynamicProperties::enum_variant(new.variant_name().unwrap())
+        }
+        _ => {
+            let changed = old.prop_len() != new.prop_len()
+                || old
+                    .iter_props()
+                    .zip(new.iter_props())
+                    .any(|(old, new)| diff_property(old, new).is_some());
+            return if changed {
+                Some(new.to_dynamic())
+            } else {
+                None
+            };
+        }
+    };
+    patch.type_name = new.type_name().to_string();
+    for (index, new_property) in new.iter_props().enumerate() {
+        let name = new.prop_name(index).unwrap();
+        let changed = match old.prop(name) {
+            Some(old_property) => diff_property(old_property, new_property),
+            None => Some(new_property.clone_prop()),
+        };
+        if let Some(changed) = changed {
+            patch.set_box(name, changed);
+        }
+    }
+
+    if patch.prop_len() == 0 {
+        None
+    } else {
+        Some(patch)
+    }
+}
+
+fn diff_property(old: &dyn Property, new: &dyn Property) -> Option<Box<dyn Property>> {
+    match (old.as_properties(), new.as_properties()) {
+        (Some(old), Some(new)) => diff(old, new).map(|patch| Box::new(patch) as Box<dyn Property>),
+        (None, None) if values_equal(old, new) => None,
+        _ => Some(new.clone_prop()),
+    }
+}
+
+fn values_equal(a: &dyn Property, b: &dyn Property) -> bool {
+    if a.type_name() != b.type_name() {
+        return false;
+    }
+
+    // values are serialized raw by formats that are not self-describing, so no registrations are needed
+    let registry = PropertyTypeRegistry::default();
+    let (a, b) = (a.serializable(&registry), b.serializable(&registry));
+    let options = bincode::DefaultOptions::new();
+    match (options.serialize(a.borrow()), options.serialize(b.borrow())) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_properties, PropertiesPatch};
+    use crate::{test_enemy as enemy, Properties, PropertiesVal, PropertyTypeRegistry};
+    use bevy_math::Vec3;
+
+    #[derive(Properties, Debug, Clone, PartialEq)]
+    enum AiState {
+        Idle,
+        Chasing { target: String, speed: f32 },
+    }
+
+    #[derive(Properties, Debug, Clone, PartialEq)]
+    struct Enemy {
+        health: f32,
+        position: Vec3,
+        path: Vec<u32>,
+        state: AiState,
+    }
+
+    #[test]
+    fn diff_and_patch() {
+        let old = enemy(10.0, Vec3::new(1.0, 2.0, 3.0), vec![1, 2], "player");
+        assert!(diff_properties(&old, &old.to_dynamic()).is_none());
+
+        let new = enemy(10.0, Vec3::new(1.0, 5.0, 3.0), vec![1, 2, 3], "wolf");
+        let patch = diff_properties(&old, &new).unwrap();
+        let names = patch
+            .properties
+            .iter_props()
+            .enumerate()
+            .map(|(index, _)| patch.properties.prop_name(index).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["position", "path", "state"]);
+        let state = patch
+            .properties
+            .prop("state")
+            .unwrap()
+            .as_properties()
+            .unwrap();
+        assert_eq!(state.variant_name(), Some("Chasing"));
+        assert_eq!(state.prop_len(), 1);
+
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register::<Vec3>();
+        let ron = patch.serialize_ron(&registry).unwrap();
+        let bytes = patch.serialize_binary(&registry).unwrap();
+        for patch in &[
+            PropertiesPatch::deserialize_ron(&ron, &registry).unwrap(),
+            PropertiesPatch::deserialize_binary(&bytes, &registry).unwrap(),
+        ] {
+            let mut patched = old.to_dynamic();
+            patch.apply(&mut patched);
+            assert!(diff_properties(&patched, &new).is_none());
+            assert_eq!(*patched.prop_val::<f32>("health").unwrap(), 10.0);
+        }
+    }
+
+    #[test]
+    fn patch_derived_properties() {
+        let old = Enemy {
+            health: 10.0,
+            position: Vec3::new(1.0, 2.0, 3.0),
+            path: vec![1, 2],
+            state: AiState::Chasing {
+                target: "player".to_string(),
+                speed: 2.0,
+            },
+        };
+        let new = Enemy {
+            path: vec![1, 2, 3],
+            state: AiState::Chasing {
+                target: "wolf".to_string(),
+                speed: 2.0,
+            },
+            ..old.clone()
+        };
+        let patch = diff_properties(&old, &new).unwrap();
+
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register::<Vec3>();
+        let ron = patch.serialize_ron(&registry).unwrap();
+        let bytes = patch.serialize_binary(&registry).unwrap();
+        for patch in &[
+            PropertiesPatch::deserialize_ron(&ron, &registry).unwrap(),
+            PropertiesPatch::deserialize_binary(&bytes, &registry).unwrap(),
+        ] {
+            let mut patched = old.clone();
+            patch.apply(&mut patched);
+            assert_eq!(patched, new);
+        }
+    }
+}
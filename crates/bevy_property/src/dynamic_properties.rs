@@ -12,6 +12,8 @@ pub struct DynamicProperties {
     pub prop_names: Vec<Cow<'static, str>>,
     pub prop_indices: HashMap<Cow<'static, str>, usize>,
     pub property_type: PropertyType,
+    /// The active variant if `property_type` is [PropertyType::Enum]
+    pub variant_name: Option<String>,
 }
 
 impl fmt::Debug for DynamicProperties {
@@ -28,6 +30,7 @@ impl fmt::Debug for DynamicProperties {
             .field("prop_names", &self.prop_names)
             .field("prop_indices", &self.prop_indices)
             .field("property_type", &self.property_type)
+            .field("variant_name", &self.variant_name)
             .finish()
     }
 }
@@ -40,6 +43,7 @@ impl DynamicProperties {
             prop_names: Default::default(),
             prop_indices: Default::default(),
             property_type: PropertyType::Map,
+            variant_name: None,
         }
     }
 
@@ -50,6 +54,20 @@ impl DynamicProperties {
             prop_names: Default::default(),
             prop_indices: Default::default(),
             property_type: PropertyType::Seq,
+            variant_name: None,
+        }
+    }
+
+    /// Creates an enum with the given active variant. Push named fields for a struct variant, unnamed fields for a
+    /// tuple variant, or nothing for a unit variant.
+    pub fn enum_variant(variant_name: &str) -> Self {
+        DynamicProperties {
+            type_name: std::any::type_name::<Self>().to_string(),
+            props: Default::default(),
+            prop_names: Default::default(),
+            prop_indices: Default::default(),
+            property_type: PropertyType::Enum,
+            variant_name: Some(variant_name.to_string()),
         }
     }
 
@@ -80,6 +98,36 @@ impl DynamicProperties {
             self.push(prop, Some(name));
         }
     }
+
+    /// Removes the property with the given name, returning it if it exists
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn Property>> {
+        let removed_index = self.prop_indices.remove(name)?;
+        self.prop_names.remove(removed_index);
+        for index in self.prop_indices.values_mut() {
+            if *index > removed_index {
+                *index -= 1;
+            }
+        }
+        Some(self.props.remove(removed_index))
+    }
+
+    /// Renames the property named `from` to `to`, replacing any property already named `to`. Returns false if there is
+    /// no property named `from`.
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        if from == to {
+            return self.prop_indices.contains_key(from);
+        }
+        if !self.prop_indices.contains_key(from) {
+            return false;
+        }
+
+        self.remove(to);
+        let index = self.prop_indices.remove(from).unwrap();
+        let cow_name: Cow<'static, str> = Cow::Owned(to.to_string());
+        self.prop_names[index] = cow_name.clone();
+        self.prop_indices.insert(cow_name, index);
+        true
+    }
 }
 
 impl Properties for DynamicProperties {
@@ -115,7 +163,9 @@ impl Properties for DynamicProperties {
     fn prop_name(&self, index: usize) -> Option<&str> {
         match self.property_type {
             PropertyType::Seq => None,
-            PropertyType::Map => self.prop_names.get(index).map(|name| name.as_ref()),
+            PropertyType::Map | PropertyType::Enum => {
+                self.prop_names.get(index).map(|name| name.as_ref())
+            }
             _ => panic!("DynamicProperties cannot be Value types"),
         }
     }
@@ -131,6 +181,11 @@ impl Properties for DynamicProperties {
             index: 0,
         }
     }
+
+    #[inline]
+    fn variant_name(&self) -> Option<&str> {
+        self.variant_name.as_deref()
+    }
 }
 
 impl Property for DynamicProperties {
@@ -189,6 +244,21 @@ impl Property for DynamicProperties {
                         }
                     }
                 }
+                PropertyType::Enum => {
+                    if properties.variant_name() != self.variant_name() {
+                        *self = properties.to_dynamic();
+                        return;
+                    }
+                    for (i, prop) in properties.iter_props().enumerate() {
+                        let p = match properties.prop_name(i) {
+                            Some(name) => self.prop_mut(name),
+                            None => self.prop_with_index_mut(i),
+                        };
+                        if let Some(p) = p {
+                            p.apply(prop);
+                        }
+                    }
+                }
                 _ => panic!("DynamicProperties cannot be Value types"),
             }
         } else {
@@ -200,6 +270,10 @@ impl Property for DynamicProperties {
         Some(self)
     }
 
+    fn as_properties_mut(&mut self) -> Option<&mut dyn Properties> {
+        Some(self)
+    }
+
     fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
         Serializable::Owned(Box::new(DynamicPropertiesSerializer::new(self, registry)))
     }
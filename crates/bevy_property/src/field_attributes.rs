@@ -0,0 +1,48 @@
+use crate::Property;
+use std::ops::RangeInclusive;
+
+/// Attributes of a field of a type that derives [Properties](crate::Properties), set with `#[property(...)]`. They are
+/// stored in the [PropertyTypeRegistration](crate::PropertyTypeRegistration) of the type, so tools like editors can
+/// read them at runtime. Fields of enum variants only support `rename` and `ignore`.
+///
+/// ```ignore
+/// #[derive(Properties, Default)]
+/// struct Health {
+///     /// Hit points at full health
+///     #[property(default = 100.0, range = 0.0..=1000.0, step = 10.0, rename = "max")]
+///     max_health: f32,
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FieldAttributes {
+    /// The name the field is accessed and serialized with. This is the name of the field unless it was set with
+    /// `#[property(rename = "...")]`.
+    pub name: &'static str,
+    /// Creates the value of the field that is used when deserialized data doesn't contain it. Set with
+    /// `#[property(default = ...)]`.
+    pub default: Option<fn() -> Box<dyn Property>>,
+    /// The values that the field is expected to have. Set with `#[property(range = min..=max)]`.
+    pub range: Option<RangeInclusive<f64>>,
+    /// The amount the field should change by when it is edited incrementally. Set with `#[property(step = ...)]`.
+    pub step: Option<f64>,
+    /// A description of the field. Set with `#[property(tooltip = "...")]`, or taken from the doc comment of the
+    /// field.
+    pub tooltip: Option<&'static str>,
+}
+
+impl FieldAttributes {
+    pub fn new(name: &'static str) -> Self {
+        FieldAttributes {
+            name,
+            default: None,
+            range: None,
+            step: None,
+            tooltip: None,
+        }
+    }
+
+    /// Returns the default value of the field, if it has one
+    pub fn default_value(&self) -> Option<Box<dyn Property>> {
+        self.default.map(|default| default())
+    }
+}
@@ -24,11 +24,11 @@ where
     }
 
     fn prop_with_index(&self, index: usize) -> Option<&dyn Property> {
-        Some(&self[index])
+        self.get(index).map(|prop| prop as &dyn Property)
     }
 
     fn prop_with_index_mut(&mut self, index: usize) -> Option<&mut dyn Property> {
-        Some(&mut self[index])
+        self.get_mut(index).map(|prop| prop as &mut dyn Property)
     }
 
     fn prop_name(&self, _index: usize) -> Option<&str> {
@@ -94,6 +94,10 @@ where
         Some(self)
     }
 
+    fn as_properties_mut(&mut self) -> Option<&mut dyn Properties> {
+        Some(self)
+    }
+
     fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a> {
         Serializable::Owned(Box::new(SeqSerializer::new(self, registry)))
     }
@@ -1,21 +1,56 @@
+pub mod binary;
 pub mod impl_property;
 pub mod property_serde;
 pub mod ron;
 
+mod diff;
 mod dynamic_properties;
+mod field_attributes;
 mod properties;
 mod property;
+mod property_path;
 mod type_registry;
 
+pub use diff::*;
 pub use dynamic_properties::*;
+pub use field_attributes::*;
 pub use properties::*;
 pub use property::*;
+pub use property_path::*;
 pub use type_registry::*;
 
+// lets the tests in this crate derive Properties
+#[cfg(test)]
+extern crate self as bevy_property;
+
 pub use bevy_property_derive::*;
 pub use erased_serde;
 pub use serde;
 
 pub mod prelude {
-    pub use crate::{DynamicProperties, Properties, PropertiesVal, Property, PropertyVal};
+    pub use crate::{
+        DynamicProperties, Properties, PropertiesVal, Property, PropertyAtPath, PropertyVal,
+    };
+}
+
+/// An enemy with a value of each kind of property: primitives, a registered value type, a sequence and an enum, for
+/// tests
+#[cfg(test)]
+pub(crate) fn test_enemy(
+    health: f32,
+    position: bevy_math::Vec3,
+    path: Vec<u32>,
+    target: &str,
+) -> DynamicProperties {
+    let mut state = DynamicProperties::enum_variant("Chasing");
+    state.type_name = "AiState".to_string();
+    state.set("target", target.to_string());
+    state.set("speed", 2.0f32);
+    let mut properties = DynamicProperties::map();
+    properties.type_name = "Enemy".to_string();
+    properties.set("health", health);
+    properties.set("position", position);
+    properties.set("path", path);
+    properties.set("state", state);
+    properties
 }
@@ -8,6 +8,10 @@ pub trait Properties: Property {
     fn prop_name(&self, index: usize) -> Option<&str>;
     fn prop_len(&self) -> usize;
     fn iter_props(&self) -> PropertyIter;
+    /// The name of the active variant if this is a [PropertyType::Enum]
+    fn variant_name(&self) -> Option<&str> {
+        None
+    }
     fn set_prop(&mut self, name: &str, value: &dyn Property) {
         if let Some(prop) = self.prop_mut(name) {
             prop.set(value);
@@ -34,6 +38,16 @@ pub trait Properties: Property {
                 }
                 dynamic_props
             }
+            PropertyType::Enum => {
+                let variant_name = self
+                    .variant_name()
+                    .expect("All properties in enums should have a variant name");
+                let mut dynamic_props = DynamicProperties::enum_variant(variant_name);
+                for (i, prop) in self.iter_props().enumerate() {
+                    dynamic_props.push(prop.clone_prop(), self.prop_name(i));
+                }
+                dynamic_props
+            }
             _ => panic!("Properties cannot be Value types"),
         };
 
@@ -1,4 +1,4 @@
-use crate::{property_serde::Serializable, Properties, PropertyTypeRegistry};
+use crate::{property_serde::Serializable, FieldAttributes, Properties, PropertyTypeRegistry};
 use erased_serde::Deserializer;
 use std::any::Any;
 
@@ -7,6 +7,9 @@ pub enum PropertyType {
     Map,
     Seq,
     Value,
+    /// An enum variant. Struct variants have named fields, tuple variants have unnamed fields and unit variants have
+    /// none.
+    Enum,
 }
 
 // TODO: consider removing send + sync requirements
@@ -23,6 +26,9 @@ pub trait Property: Send + Sync + Any + 'static {
     fn as_properties(&self) -> Option<&dyn Properties> {
         None
     }
+    fn as_properties_mut(&mut self) -> Option<&mut dyn Properties> {
+        None
+    }
     fn serializable<'a>(&'a self, registry: &'a PropertyTypeRegistry) -> Serializable<'a>;
 }
 
@@ -31,6 +37,14 @@ pub trait DeserializeProperty {
         deserializer: &mut dyn Deserializer,
         property_type_registry: &PropertyTypeRegistry,
     ) -> Result<Box<dyn Property>, erased_serde::Error>;
+
+    /// The attributes of the fields of this type. See [FieldAttributes].
+    fn field_attributes() -> Vec<FieldAttributes>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
 }
 
 pub trait PropertyVal {
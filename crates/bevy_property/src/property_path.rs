@@ -0,0 +1,409 @@
+use crate::{property_serde::deserialize_primitive, Property, PropertyTypeRegistry};
+use bevy_math::{Vec2, Vec3};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PropertyPathError {
+    #[error("Invalid property path \"{path}\": {reason}.")]
+    InvalidPath { path: String, reason: &'static str },
+    #[error("{type_name} has no property at \"{segment}\".")]
+    MissingProperty { type_name: String, segment: String },
+    #[error("{type_name} is not registered, so values of it cannot be parsed.")]
+    UnregisteredType { type_name: String },
+    #[error("Failed to parse a {type_name} value: {message}")]
+    InvalidValue { type_name: String, message: String },
+}
+
+/// One step of a [PropertyPath]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A named property. Numeric names also address sequence and tuple variant properties by index.
+    Field(String),
+    /// A property in a sequence
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, "{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+/// A path to a nested property, such as `translation.x` or `children[2].name`. Field names are separated by `.` and
+/// sequence indices are written in brackets.
+///
+/// The `x`, `y` and `z` components of [Vec2] and [Vec3] values can be addressed as fields even though vectors are
+/// `Value` properties.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropertyPath {
+    pub segments: Vec<PathSegment>,
+}
+
+impl PropertyPath {
+    pub fn parse(path: &str) -> Result<Self, PropertyPathError> {
+        let invalid = |reason| PropertyPathError::InvalidPath {
+            path: path.to_string(),
+            reason,
+        };
+        let mut segments = Vec::new();
+        let mut rest = path;
+        while !rest.is_empty() {
+            if let Some(index_start) = rest.strip_prefix('[') {
+                let end = index_start
+                    .find(']')
+                    .ok_or_else(|| invalid("unclosed '['"))?;
+                let index = index_start[..end]
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| invalid("indices must be non-negative integers"))?;
+                segments.push(PathSegment::Index(index));
+                rest = &index_start[end + 1..];
+            } else {
+                let end = rest.find(&['.', '['][..]).unwrap_or(rest.len());
+                if end == 0 {
+                    return Err(invalid("field names cannot be empty"));
+                }
+                segments.push(PathSegment::Field(rest[..end].to_string()));
+                rest = &rest[end..];
+            }
+
+            if let Some(next) = rest.strip_prefix('.') {
+                if next.is_empty() {
+                    return Err(invalid("paths cannot end with '.'"));
+                }
+                rest = next;
+            } else if !rest.is_empty() && !rest.starts_with('[') {
+                return Err(invalid("expected '.' or '[' after ']'"));
+            }
+        }
+
+        if segments.is_empty() {
+            return Err(invalid("paths cannot be empty"));
+        }
+
+        Ok(PropertyPath { segments })
+    }
+
+    /// Returns the property at this path, starting from `property`
+    pub fn get<'a>(
+        &self,
+        property: &'a dyn Property,
+    ) -> Result<&'a dyn Property, PropertyPathError> {
+        let mut current = property;
+        for segment in self.segments.iter() {
+            current = get_segment(current, segment).ok_or_else(|| {
+                PropertyPathError::MissingProperty {
+                    type_name: current.type_name().to_string(),
+                    segment: segment.to_string(),
+                }
+            })?;
+        }
+
+        Ok(current)
+    }
+
+    pub fn get_mut<'a>(
+        &self,
+        property: &'a mut dyn Property,
+    ) -> Result<&'a mut dyn Property, PropertyPathError> {
+        let mut current = property;
+        for segment in self.segments.iter() {
+            let type_name = current.type_name().to_string();
+            current = get_segment_mut(current, segment).ok_or_else(|| {
+                PropertyPathError::MissingProperty {
+                    type_name,
+                    segment: segment.to_string(),
+                }
+            })?;
+        }
+
+        Ok(current)
+    }
+
+    /// Sets the property at this path, starting from `property`. Like [Property::set], this panics if `value` cannot
+    /// be converted to the type of the property at this path.
+    pub fn set(
+        &self,
+        property: &mut dyn Property,
+        value: &dyn Property,
+    ) -> Result<(), PropertyPathError> {
+        self.get_mut(property)?.set(value);
+        Ok(())
+    }
+
+    /// Parses `value` as RON for the type of the property at this path and sets it. The type must be registered in
+    /// `registry`.
+    pub fn set_ron(
+        &self,
+        property: &mut dyn Property,
+        value: &str,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<(), PropertyPathError> {
+        let target = self.get_mut(property)?;
+        let value = parse_ron_value(target.type_name(), value, registry)?;
+        target.set(&*value);
+        Ok(())
+    }
+}
+
+impl FromStr for PropertyPath {
+    type Err = PropertyPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        PropertyPath::parse(path)
+    }
+}
+
+impl fmt::Display for PropertyPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            if i > 0 && matches!(segment, PathSegment::Field(_)) {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses `value` as RON for the type with the given name. The type must either be registered in `registry` or be a
+/// primitive such as `f32`, `bool` or `String`.
+pub fn parse_ron_value(
+    type_name: &str,
+    value: &str,
+    registry: &PropertyTypeRegistry,
+) -> Result<Box<dyn Property>, PropertyPathError> {
+    let invalid_value = |message: String| PropertyPathError::InvalidValue {
+        type_name: type_name.to_string(),
+        message,
+    };
+    let mut deserializer =
+        ron::de::Deserializer::from_str(value).map_err(|err| invalid_value(err.to_string()))?;
+    if let Some(registration) = registry.get(type_name) {
+        return registration
+            .deserialize(&mut deserializer, registry)
+            .map_err(|err| invalid_value(err.to_string()));
+    }
+
+    match deserialize_primitive(type_name, &mut deserializer) {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) => Err(PropertyPathError::UnregisteredType {
+            type_name: type_name.to_string(),
+        }),
+        Err(err) => Err(invalid_value(err.to_string())),
+    }
+}
+
+/// Accesses the properties of a path. This is implemented for all [Properties](crate::Properties) and for
+/// `dyn Property`.
+pub trait PropertyAtPath {
+    fn path(&self, path: &str) -> Result<&dyn Property, PropertyPathError>;
+    fn path_mut(&mut self, path: &str) -> Result<&mut dyn Property, PropertyPathError>;
+    fn set_path(&mut self, path: &str, value: &dyn Property) -> Result<(), PropertyPathError>;
+}
+
+impl PropertyAtPath for dyn Property {
+    fn path(&self, path: &str) -> Result<&dyn Property, PropertyPathError> {
+        PropertyPath::parse(path)?.get(self)
+    }
+
+    fn path_mut(&mut self, path: &str) -> Result<&mut dyn Property, PropertyPathError> {
+        PropertyPath::parse(path)?.get_mut(self)
+    }
+
+    fn set_path(&mut self, path: &str, value: &dyn Property) -> Result<(), PropertyPathError> {
+        PropertyPath::parse(path)?.set(self, value)
+    }
+}
+
+impl<P> PropertyAtPath for P
+where
+    P: crate::Properties,
+{
+    fn path(&self, path: &str) -> Result<&dyn Property, PropertyPathError> {
+        PropertyPath::parse(path)?.get(self)
+    }
+
+    fn path_mut(&mut self, path: &str) -> Result<&mut dyn Property, PropertyPathError> {
+        PropertyPath::parse(path)?.get_mut(self)
+    }
+
+    fn set_path(&mut self, path: &str, value: &dyn Property) -> Result<(), PropertyPathError> {
+        PropertyPath::parse(path)?.set(self, value)
+    }
+}
+
+fn get_segment<'a>(property: &'a dyn Property, segment: &PathSegment) -> Option<&'a dyn Property> {
+    if let Some(properties) = property.as_properties() {
+        return match segment {
+            PathSegment::Field(name) => properties.prop(name).or_else(|| {
+                let index = name.parse::<usize>().ok()?;
+                properties.prop_with_index(index)
+            }),
+            PathSegment::Index(index) => properties.prop_with_index(*index),
+        };
+    }
+
+    let any = property.any();
+    match segment {
+        PathSegment::Field(name) => {
+            if let Some(vec) = any.downcast_ref::<Vec2>() {
+                match name.as_str() {
+                    "x" => Some(&vec[0]),
+                    "y" => Some(&vec[1]),
+                    _ => None,
+                }
+            } else if let Some(vec) = any.downcast_ref::<Vec3>() {
+                match name.as_str() {
+                    "x" => Some(&vec[0]),
+                    "y" => Some(&vec[1]),
+                    "z" => Some(&vec[2]),
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        }
+        PathSegment::Index(_) => None,
+    }
+}
+
+fn get_segment_mut<'a>(
+    property: &'a mut dyn Property,
+    segment: &PathSegment,
+) -> Option<&'a mut dyn Property> {
+    if property.as_properties().is_some() {
+        let properties = property.as_properties_mut()?;
+        return match segment {
+            PathSegment::Field(name) => {
+                if properties.prop(name).is_some() {
+                    properties.prop_mut(name)
+                } else {
+                    let index = name.parse::<usize>().ok()?;
+                    properties.prop_with_index_mut(index)
+                }
+            }
+            PathSegment::Index(index) => properties.prop_with_index_mut(*index),
+        };
+    }
+
+    let any = property.any_mut();
+    match segment {
+        PathSegment::Field(name) => {
+            if any.is::<Vec2>() {
+                let vec = any.downcast_mut::<Vec2>().unwrap();
+                match name.as_str() {
+                    "x" => Some(vec.x_mut()),
+                    "y" => Some(vec.y_mut()),
+                    _ => None,
+                }
+            } else if let Some(vec) = any.downcast_mut::<Vec3>() {
+                match name.as_str() {
+                    "x" => Some(vec.x_mut()),
+                    "y" => Some(vec.y_mut()),
+                    "z" => Some(vec.z_mut()),
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        }
+        PathSegment::Index(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathSegment, PropertyAtPath, PropertyPath, PropertyPathError};
+    use crate::{DynamicProperties, PropertyTypeRegistry, PropertyVal};
+    use bevy_math::Vec3;
+
+    #[test]
+    fn parse_paths() {
+        let path = PropertyPath::parse("children[2].translation.x").unwrap();
+        assert_eq!(
+            path.segments,
+            vec![
+                PathSegment::Field("children".to_string()),
+                PathSegment::Index(2),
+                PathSegment::Field("translation".to_string()),
+                PathSegment::Field("x".to_string()),
+            ]
+        );
+        assert_eq!(path.to_string(), "children[2].translation.x");
+
+        for invalid in &["", "a.", ".a", "a..b", "a[1", "a[-1]", "a[1]b"] {
+            assert!(matches!(
+                PropertyPath::parse(invalid),
+                Err(PropertyPathError::InvalidPath { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn get_and_set_nested_properties() {
+        let mut margin = DynamicProperties::map();
+        margin.set("left", 1.0f32);
+        let mut style = DynamicProperties::map();
+        style.set("margin", margin);
+        style.set("translation", Vec3::new(1.0, 2.0, 3.0));
+        style.set("weights", vec![0.5f32, 0.25]);
+
+        assert_eq!(
+            *style.path("margin.left").unwrap().val::<f32>().unwrap(),
+            1.0
+        );
+        assert_eq!(
+            *style.path("weights[1]").unwrap().val::<f32>().unwrap(),
+            0.25
+        );
+        assert_eq!(
+            *style.path("translation.y").unwrap().val::<f32>().unwrap(),
+            2.0
+        );
+        assert!(matches!(
+            style.path("margin.right"),
+            Err(PropertyPathError::MissingProperty { .. })
+        ));
+        assert!(style.path("weights[2]").is_err());
+
+        style.set_path("margin.left", &4.0f32).unwrap();
+        style.set_path("translation.z", &5.0f32).unwrap();
+        let registry = PropertyTypeRegistry::default();
+        PropertyPath::parse("weights.0")
+            .unwrap()
+            .set_ron(&mut style, "0.75", &registry)
+            .unwrap();
+        assert_eq!(
+            *style.path("margin.left").unwrap().val::<f32>().unwrap(),
+            4.0
+        );
+        assert_eq!(
+            *style.path("translation").unwrap().val::<Vec3>().unwrap(),
+            Vec3::new(1.0, 2.0, 5.0)
+        );
+        assert_eq!(
+            *style.path("weights[0]").unwrap().val::<f32>().unwrap(),
+            0.75
+        );
+
+        assert!(matches!(
+            PropertyPath::parse("translation").unwrap().set_ron(
+                &mut style,
+                "(1.0, 2.0, 3.0)",
+                &registry
+            ),
+            Err(PropertyPathError::UnregisteredType { .. })
+        ));
+        assert!(matches!(
+            PropertyPath::parse("margin.left")
+                .unwrap()
+                .set_ron(&mut style, "\"left\"", &registry),
+            Err(PropertyPathError::InvalidValue { .. })
+        ));
+    }
+}
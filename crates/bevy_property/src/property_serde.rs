@@ -2,14 +2,16 @@ use crate::{DynamicProperties, Properties, Property, PropertyType, PropertyTypeR
 use de::SeqAccess;
 use serde::{
     de::{self, DeserializeSeed, MapAccess, Visitor},
-    ser::{SerializeMap, SerializeSeq},
-    Serialize,
+    ser::{SerializeMap, SerializeSeq, SerializeTuple},
+    Deserialize, Serialize,
 };
+use std::borrow::Cow;
 
 pub const TYPE_FIELD: &str = "type";
 pub const MAP_FIELD: &str = "map";
 pub const SEQ_FIELD: &str = "seq";
 pub const VALUE_FIELD: &str = "value";
+pub const VARIANT_FIELD: &str = "variant";
 
 pub enum Serializable<'a> {
     Owned(Box<dyn erased_serde::Serialize + 'a>),
@@ -51,6 +53,11 @@ where
     where
         S: serde::Serializer,
     {
+        // formats that are not self-describing write the type name next to the value. see BinaryPropertySerializer
+        if !serializer.is_human_readable() {
+            return self.property.serialize(serializer);
+        }
+
         let mut state = serializer.serialize_map(Some(2))?;
         state.serialize_entry(
             TYPE_FIELD,
@@ -83,6 +90,11 @@ impl<'a> Serialize for DynamicPropertiesSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return BinaryPropertiesSerializer::new(self.dynamic_properties, self.registry)
+                .serialize(serializer);
+        }
+
         match self.dynamic_properties.property_type {
             PropertyType::Map => {
                 MapSerializer::new(self.dynamic_properties, self.registry).serialize(serializer)
@@ -90,6 +102,9 @@ impl<'a> Serialize for DynamicPropertiesSerializer<'a> {
             PropertyType::Seq => {
                 SeqSerializer::new(self.dynamic_properties, self.registry).serialize(serializer)
             }
+            PropertyType::Enum => {
+                EnumSerializer::new(self.dynamic_properties, self.registry).serialize(serializer)
+            }
             _ => Err(serde::ser::Error::custom(
                 "DynamicProperties cannot be Value type",
             )),
@@ -115,11 +130,41 @@ fn format_type_name<'a>(registry: &'a PropertyTypeRegistry, type_name: &'a str)
     registry.format_type_name(type_name).unwrap_or(type_name)
 }
 
+/// Replaces serialized names of registered types, such as stable names and aliases, with their full type names
+fn resolve_type_name(registry: &PropertyTypeRegistry, type_name: String) -> String {
+    registry
+        .get(&type_name)
+        .map(|registration| registration.name.to_string())
+        .unwrap_or(type_name)
+}
+
+/// Adds the fields of a deserialized map that are missing from the data and have a default value in the registration
+/// of its type. See [FieldAttributes::default](crate::FieldAttributes::default).
+fn add_default_fields(registry: &PropertyTypeRegistry, dynamic_properties: &mut DynamicProperties) {
+    if dynamic_properties.property_type != PropertyType::Map {
+        return;
+    }
+    if let Some(registration) = registry.get_with_full_name(&dynamic_properties.type_name) {
+        for field in registration.fields.iter() {
+            if dynamic_properties.prop(field.name).is_none() {
+                if let Some(value) = field.default_value() {
+                    dynamic_properties.set_box(field.name, value);
+                }
+            }
+        }
+    }
+}
+
 impl<'a> Serialize for MapSerializer<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return BinaryPropertiesSerializer::new(self.properties, self.registry)
+                .serialize(serializer);
+        }
+
         let mut state = serializer.serialize_map(Some(2))?;
 
         state.serialize_entry(
@@ -175,6 +220,11 @@ impl<'a> Serialize for SeqSerializer<'a> {
     where
         S: serde::Serializer,
     {
+        if !serializer.is_human_readable() {
+            return BinaryPropertiesSerializer::new(self.properties, self.registry)
+                .serialize(serializer);
+        }
+
         let mut state = serializer.serialize_map(Some(2))?;
         state.serialize_entry(
             TYPE_FIELD,
@@ -209,6 +259,66 @@ impl<'a> Serialize for SeqValueSerializer<'a> {
     }
 }
 
+/// Serializes an enum as its type, its variant name, and then its fields: a `map` for struct variants, a `seq` for tuple
+/// variants, or nothing for unit variants
+pub struct EnumSerializer<'a> {
+    pub properties: &'a dyn Properties,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> EnumSerializer<'a> {
+    pub fn new(properties: &'a dyn Properties, registry: &'a PropertyTypeRegistry) -> Self {
+        EnumSerializer {
+            properties,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for EnumSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if !serializer.is_human_readable() {
+            return BinaryPropertiesSerializer::new(self.properties, self.registry)
+                .serialize(serializer);
+        }
+
+        let variant_name = self
+            .properties
+            .variant_name()
+            .ok_or_else(|| serde::ser::Error::custom("Enums must have a variant name"))?;
+        let has_fields = self.properties.prop_len() > 0;
+        let mut state = serializer.serialize_map(Some(if has_fields { 3 } else { 2 }))?;
+        state.serialize_entry(
+            TYPE_FIELD,
+            format_type_name(self.registry, self.properties.type_name()),
+        )?;
+        state.serialize_entry(VARIANT_FIELD, variant_name)?;
+        if has_fields {
+            if self.properties.prop_name(0).is_some() {
+                state.serialize_entry(
+                    MAP_FIELD,
+                    &MapValueSerializer {
+                        properties: self.properties,
+                        registry: self.registry,
+                    },
+                )?;
+            } else {
+                state.serialize_entry(
+                    SEQ_FIELD,
+                    &SeqValueSerializer {
+                        properties: self.properties,
+                        registry: self.registry,
+                    },
+                )?;
+            }
+        }
+        state.end()
+    }
+}
+
 pub struct DynamicPropertiesDeserializer<'a> {
     registry: &'a PropertyTypeRegistry,
 }
@@ -226,6 +336,15 @@ impl<'a, 'de> DeserializeSeed<'de> for DynamicPropertiesDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
+        if !deserializer.is_human_readable() {
+            return match BinaryPropertyDeserializer::new(self.registry)
+                .deserialize_dynamic_properties_or_property(deserializer)?
+            {
+                DynamicPropertiesOrProperty::DynamicProperties(value) => Ok(value),
+                _ => Err(de::Error::custom("Expected DynamicProperties")),
+            };
+        }
+
         deserializer.deserialize_map(DynamicPropertiesVisiter {
             registry: self.registry,
         })
@@ -499,18 +618,27 @@ where
     V: MapAccess<'de>,
 {
     let mut type_name: Option<String> = None;
+    let mut variant_name: Option<String> = None;
     while let Some(key) = map.next_key::<String>()? {
         match key.as_str() {
             TYPE_FIELD => {
                 type_name = Some(map.next_value()?);
             }
+            VARIANT_FIELD => {
+                variant_name = Some(map.next_value()?);
+            }
             MAP_FIELD => {
                 let type_name = type_name
                     .take()
                     .ok_or_else(|| de::Error::missing_field(TYPE_FIELD))?;
                 let mut dynamic_properties =
                     map.next_value_seed(MapPropertyDeserializer { registry })?;
-                dynamic_properties.type_name = type_name;
+                dynamic_properties.type_name = resolve_type_name(registry, type_name);
+                if variant_name.is_some() {
+                    dynamic_properties.property_type = PropertyType::Enum;
+                    dynamic_properties.variant_name = variant_name;
+                }
+                add_default_fields(registry, &mut dynamic_properties);
                 return Ok(DynamicPropertiesOrProperty::DynamicProperties(
                     dynamic_properties,
                 ));
@@ -521,7 +649,11 @@ where
                     .ok_or_else(|| de::Error::missing_field(TYPE_FIELD))?;
                 let mut dynamic_properties =
                     map.next_value_seed(SeqPropertyDeserializer { registry })?;
-                dynamic_properties.type_name = type_name;
+                dynamic_properties.type_name = resolve_type_name(registry, type_name);
+                if variant_name.is_some() {
+                    dynamic_properties.property_type = PropertyType::Enum;
+                    dynamic_properties.variant_name = variant_name;
+                }
                 return Ok(DynamicPropertiesOrProperty::DynamicProperties(
                     dynamic_properties,
                 ));
@@ -541,5 +673,528 @@ where
         }
     }
 
-    Err(de::Error::custom("Maps in this location must have the \'type\' field and one of the following fields: \'map\', \'seq\', \'value\'"))
+    // enums with unit variants have no fields after their variant name
+    if let (Some(type_name), Some(variant_name)) = (type_name, variant_name) {
+        let mut dynamic_properties = DynamicProperties::enum_variant(&variant_name);
+        dynamic_properties.type_name = resolve_type_name(registry, type_name);
+        return Ok(DynamicPropertiesOrProperty::DynamicProperties(
+            dynamic_properties,
+        ));
+    }
+
+    Err(de::Error::custom("Maps in this location must have the \'type\' field and one of the following fields: \'map\', \'seq\', \'value\', \'variant\'"))
+}
+
+/// Deserializes a primitive with the given type name, returning `None` if the type is not a primitive. Primitives
+/// cannot be registered in a [PropertyTypeRegistry], so paths that need to know the type of every value use this.
+pub(crate) fn deserialize_primitive<'de, D>(
+    type_name: &str,
+    deserializer: D,
+) -> Result<Option<Box<dyn Property>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match BinaryType::primitive(type_name) {
+        Some(binary_type) => binary_type.deserialize_primitive(deserializer),
+        None => Ok(None),
+    }
+}
+
+macro_rules! binary_types {
+    ($($variant:ident => $ty:ty),*) => {
+        /// The type of a property written by [BinaryPropertySerializer]. Primitives are written as a one byte tag, and
+        /// other types by name. New primitives must be added at the end, so that existing data keeps its meaning.
+        #[derive(Serialize, Deserialize)]
+        enum BinaryType<'a> {
+            Named(Cow<'a, str>),
+            $($variant,)*
+        }
+
+        impl<'a> BinaryType<'a> {
+            fn primitive(type_name: &str) -> Option<BinaryType<'a>> {
+                $(
+                    if type_name == std::any::type_name::<$ty>() {
+                        return Some(BinaryType::$variant);
+                    }
+                )*
+                None
+            }
+
+            fn type_name(&self) -> &str {
+                match self {
+                    BinaryType::Named(type_name) => type_name,
+                    $(BinaryType::$variant => std::any::type_name::<$ty>(),)*
+                }
+            }
+
+            /// Deserializes a value of this type, or returns `None` if this is not a primitive type
+            fn deserialize_primitive<'de, D>(
+                &self,
+                deserializer: D,
+            ) -> Result<Option<Box<dyn Property>>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                match self {
+                    BinaryType::Named(_) => Ok(None),
+                    $(
+                        BinaryType::$variant => {
+                            let value = <$ty as Deserialize>::deserialize(deserializer)?;
+                            Ok(Some(Box::new(value)))
+                        }
+                    )*
+                }
+            }
+        }
+    };
+}
+
+binary_types!(
+    Bool => bool,
+    String => String,
+    Usize => usize,
+    U64 => u64,
+    U32 => u32,
+    U16 => u16,
+    U8 => u8,
+    Isize => isize,
+    I64 => i64,
+    I32 => i32,
+    I16 => i16,
+    I8 => i8,
+    F32 => f32,
+    F64 => f64
+);
+
+impl<'a> BinaryType<'a> {
+    fn of(registry: &'a PropertyTypeRegistry, type_name: &'a str) -> BinaryType<'a> {
+        BinaryType::primitive(type_name).unwrap_or_else(|| {
+            BinaryType::Named(Cow::Borrowed(format_type_name(registry, type_name)))
+        })
+    }
+}
+
+fn property_type_to_u8(property_type: PropertyType) -> u8 {
+    match property_type {
+        PropertyType::Value => 0,
+        PropertyType::Map => 1,
+        PropertyType::Seq => 2,
+        PropertyType::Enum => 3,
+    }
+}
+
+fn property_type_from_u8(property_type: u8) -> Option<PropertyType> {
+    match property_type {
+        0 => Some(PropertyType::Value),
+        1 => Some(PropertyType::Map),
+        2 => Some(PropertyType::Seq),
+        3 => Some(PropertyType::Enum),
+        _ => None,
+    }
+}
+
+/// Serializes a property for formats that are not self-describing, such as bincode. These formats cannot be read
+/// without knowing the type of every value up front, so each property is written as a tuple of its type, its
+/// [PropertyType], its variant name, and then either its value or its list of `(name, property)` pairs. Primitive types
+/// are written as a one byte tag, and other types by name.
+///
+/// The RON serializers switch to this layout when [serde::Serializer::is_human_readable] returns false.
+pub struct BinaryPropertySerializer<'a> {
+    pub property: &'a dyn Property,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> BinaryPropertySerializer<'a> {
+    pub fn new(property: &'a dyn Property, registry: &'a PropertyTypeRegistry) -> Self {
+        BinaryPropertySerializer { property, registry }
+    }
+}
+
+impl<'a> Serialize for BinaryPropertySerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if let Some(properties) = self.property.as_properties() {
+            return BinaryPropertiesSerializer::new(properties, self.registry)
+                .serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_tuple(4)?;
+        state.serialize_element(&BinaryType::of(self.registry, self.property.type_name()))?;
+        state.serialize_element(&property_type_to_u8(PropertyType::Value))?;
+        state.serialize_element(&None::<&str>)?;
+        state.serialize_element(self.property.serializable(self.registry).borrow())?;
+        state.end()
+    }
+}
+
+/// The [BinaryPropertySerializer] layout for [Properties]
+pub struct BinaryPropertiesSerializer<'a> {
+    pub properties: &'a dyn Properties,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> BinaryPropertiesSerializer<'a> {
+    pub fn new(properties: &'a dyn Properties, registry: &'a PropertyTypeRegistry) -> Self {
+        BinaryPropertiesSerializer {
+            properties,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for BinaryPropertiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_tuple(4)?;
+        state.serialize_element(&BinaryType::of(self.registry, self.properties.type_name()))?;
+        state.serialize_element(&property_type_to_u8(self.properties.property_type()))?;
+        state.serialize_element(&self.properties.variant_name())?;
+        state.serialize_element(&BinaryFieldsSerializer {
+            properties: self.properties,
+            registry: self.registry,
+        })?;
+        state.end()
+    }
+}
+
+struct BinaryFieldsSerializer<'a> {
+    properties: &'a dyn Properties,
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for BinaryFieldsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.properties.prop_len()))?;
+        for (index, property) in self.properties.iter_props().enumerate() {
+            state.serialize_element(&(
+                self.properties.prop_name(index),
+                BinaryPropertySerializer::new(property, self.registry),
+            ))?;
+        }
+        state.end()
+    }
+}
+
+/// Deserializes properties written by [BinaryPropertySerializer]. Values are deserialized as the type written next to
+/// them, which must either be registered or be a primitive.
+pub struct BinaryPropertyDeserializer<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> BinaryPropertyDeserializer<'a> {
+    pub fn new(registry: &'a PropertyTypeRegistry) -> Self {
+        BinaryPropertyDeserializer { registry }
+    }
+
+    fn deserialize_dynamic_properties_or_property<'de, D>(
+        self,
+        deserializer: D,
+    ) -> Result<DynamicPropertiesOrProperty, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            4,
+            BinaryPropertyVisiter {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryPropertyDeserializer<'a> {
+    type Value = Box<dyn Property>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(
+            match self.deserialize_dynamic_properties_or_property(deserializer)? {
+                DynamicPropertiesOrProperty::DynamicProperties(value) => Box::new(value),
+                DynamicPropertiesOrProperty::Property(value) => value,
+            },
+        )
+    }
+}
+
+struct BinaryPropertyVisiter<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryPropertyVisiter<'a> {
+    type Value = DynamicPropertiesOrProperty;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("binary property")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let binary_type: BinaryType = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let property_type: u8 = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let property_type = property_type_from_u8(property_type)
+            .ok_or_else(|| de::Error::custom(format!("Invalid property type {}", property_type)))?;
+        let variant_name: Option<String> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+
+        if property_type == PropertyType::Value {
+            let value = seq
+                .next_element_seed(BinaryValueDeserializer {
+                    binary_type: &binary_type,
+                    registry: self.registry,
+                })?
+                .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+            return Ok(DynamicPropertiesOrProperty::Property(value));
+        }
+
+        let mut dynamic_properties = match variant_name {
+            Some(variant_name) if property_type == PropertyType::Enum => {
+                DynamicProperties::enum_variant(&variant_name)
+            }
+            None if property_type == PropertyType::Map => DynamicProperties::map(),
+            None if property_type == PropertyType::Seq => DynamicProperties::seq(),
+            _ => return Err(de::Error::custom("Only enums can have a variant name")),
+        };
+        dynamic_properties.type_name =
+            resolve_type_name(self.registry, binary_type.type_name().to_string());
+        let fields = seq
+            .next_element_seed(BinaryFieldsDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        for (name, property) in fields {
+            dynamic_properties.push(property, name.as_deref());
+        }
+        add_default_fields(self.registry, &mut dynamic_properties);
+
+        Ok(DynamicPropertiesOrProperty::DynamicProperties(
+            dynamic_properties,
+        ))
+    }
+}
+
+struct BinaryValueDeserializer<'a> {
+    binary_type: &'a BinaryType<'a>,
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryValueDeserializer<'a> {
+    type Value = Box<dyn Property>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if let BinaryType::Named(type_name) = self.binary_type {
+            return match self.registry.get(type_name) {
+                Some(registration) => registration.deserialize(deserializer, self.registry),
+                None => Err(de::Error::custom(format!(
+                    "TypeRegistration is missing for {}",
+                    type_name
+                ))),
+            };
+        }
+
+        Ok(self
+            .binary_type
+            .deserialize_primitive(deserializer)?
+            .expect("Types that are not named are primitives"))
+    }
+}
+
+struct BinaryFieldsDeserializer<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryFieldsDeserializer<'a> {
+    type Value = Vec<(Option<String>, Box<dyn Property>)>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(BinaryFieldsVisiter {
+            registry: self.registry,
+        })
+    }
+}
+
+struct BinaryFieldsVisiter<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryFieldsVisiter<'a> {
+    type Value = Vec<(Option<String>, Box<dyn Property>)>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of binary properties")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        // the length comes from the data, so it is capped like serde's own collections to not trust it too much
+        let mut fields = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(field) = seq.next_element_seed(BinaryFieldDeserializer {
+            registry: self.registry,
+        })? {
+            fields.push(field);
+        }
+        Ok(fields)
+    }
+}
+
+struct BinaryFieldDeserializer<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BinaryFieldDeserializer<'a> {
+    type Value = (Option<String>, Box<dyn Property>);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(
+            2,
+            BinaryFieldVisiter {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct BinaryFieldVisiter<'a> {
+    registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BinaryFieldVisiter<'a> {
+    type Value = (Option<String>, Box<dyn Property>);
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("named binary property")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let name: Option<String> = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let property = seq
+            .next_element_seed(BinaryPropertyDeserializer::new(self.registry))?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok((name, property))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinaryType, DynamicPropertiesSerializer};
+    use crate::{
+        binary, ron::deserialize_dynamic_properties, test_enemy, DynamicProperties, Properties,
+        PropertiesVal, PropertyType, PropertyTypeRegistry, PropertyVal,
+    };
+    use bevy_math::Vec3;
+    use bincode::Options;
+
+    fn round_trip(properties: &DynamicProperties) -> DynamicProperties {
+        let registry = PropertyTypeRegistry::default();
+        let ron_string =
+            ron::ser::to_string(&DynamicPropertiesSerializer::new(properties, &registry)).unwrap();
+        deserialize_dynamic_properties(&ron_string, &registry).unwrap()
+    }
+
+    #[test]
+    fn enum_variants_round_trip() {
+        let mut struct_variant = DynamicProperties::enum_variant("Chasing");
+        struct_variant.set("target", "player".to_string());
+        let loaded = round_trip(&struct_variant);
+        assert_eq!(loaded.property_type, PropertyType::Enum);
+        assert_eq!(loaded.variant_name(), Some("Chasing"));
+        assert_eq!(loaded.prop_name(0), Some("target"));
+        assert_eq!(
+            loaded.prop("target").unwrap().val::<String>().unwrap(),
+            "player"
+        );
+
+        let mut tuple_variant = DynamicProperties::enum_variant("Fleeing");
+        tuple_variant.push(Box::new("wolf".to_string()), None);
+        let loaded = round_trip(&tuple_variant);
+        assert_eq!(loaded.variant_name(), Some("Fleeing"));
+        assert_eq!(loaded.prop_name(0), None);
+        assert_eq!(
+            loaded.prop_with_index(0).unwrap().val::<String>().unwrap(),
+            "wolf"
+        );
+
+        let loaded = round_trip(&DynamicProperties::enum_variant("Idle"));
+        assert_eq!(loaded.property_type, PropertyType::Enum);
+        assert_eq!(loaded.variant_name(), Some("Idle"));
+        assert_eq!(loaded.prop_len(), 0);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register::<Vec3>();
+
+        let properties = test_enemy(7.5, Vec3::new(1.0, 2.0, 3.0), vec![1, 2, 3], "player");
+
+        let bytes = binary::serialize_dynamic_properties(&properties, &registry).unwrap();
+        // primitives are written as tags rather than type names
+        assert!(!bytes.windows(3).any(|window| window == b"f32"));
+        let loaded = binary::deserialize_dynamic_properties(&bytes, &registry).unwrap();
+        assert_eq!(loaded.type_name, "Enemy");
+        assert_eq!(loaded.property_type, PropertyType::Map);
+        assert_eq!(*loaded.prop_val::<f32>("health").unwrap(), 7.5);
+        assert_eq!(
+            *loaded.prop_val::<Vec3>("position").unwrap(),
+            Vec3::new(1.0, 2.0, 3.0)
+        );
+
+        let path = loaded.prop("path").unwrap().as_properties().unwrap();
+        assert_eq!(path.property_type(), PropertyType::Seq);
+        assert_eq!(path.prop_len(), 3);
+        assert_eq!(path.prop_with_index(2).unwrap().val::<u32>(), Some(&3));
+
+        let state = loaded.prop("state").unwrap().as_properties().unwrap();
+        assert_eq!(state.variant_name(), Some("Chasing"));
+        assert_eq!(
+            state.prop("target").unwrap().val::<String>().unwrap(),
+            "player"
+        );
+    }
+
+    #[test]
+    fn binary_field_counts_are_not_trusted() {
+        // a map that claims to have u64::MAX fields, followed by none
+        let bytes = bincode::DefaultOptions::new()
+            .serialize(&(
+                BinaryType::Named("Enemy".into()),
+                1u8,
+                None::<String>,
+                u64::MAX,
+            ))
+            .unwrap();
+        let registry = PropertyTypeRegistry::default();
+        assert!(binary::deserialize_dynamic_properties(&bytes, &registry).is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{DeserializeProperty, Property};
+use crate::{DeserializeProperty, FieldAttributes, Property};
 use bevy_utils::{HashMap, HashSet};
 use std::{any::TypeId, fmt};
 
@@ -7,6 +7,8 @@ pub struct PropertyTypeRegistry {
     registrations: HashMap<String, PropertyTypeRegistration>,
     short_names: HashMap<String, String>,
     ambigous_names: HashSet<String>,
+    /// Stable names and aliases, mapped to the full type name they refer to
+    names: HashMap<String, String>,
 }
 
 impl PropertyTypeRegistry {
@@ -32,17 +34,56 @@ impl PropertyTypeRegistry {
             .insert(registration.name.to_string(), registration);
     }
 
+    /// Gives the registered type `T` a name that is used instead of its Rust type name when it is serialized, so that
+    /// moving or renaming `T` doesn't break saved data. The type name of `T` still resolves to `T`. Panics if `T`
+    /// has not been registered.
+    pub fn set_stable_name<T: 'static>(&mut self, stable_name: &str) {
+        let type_name = std::any::type_name::<T>();
+        let registration = self.registrations.get_mut(type_name).unwrap_or_else(|| {
+            panic!(
+                "Type {} must be registered before it is given a stable name",
+                type_name
+            )
+        });
+        if let Some(old_name) = registration.stable_name.replace(stable_name.to_string()) {
+            self.names.remove(&old_name);
+        }
+        self.names
+            .insert(stable_name.to_string(), type_name.to_string());
+    }
+
+    /// Makes `alias` resolve to the registered type `T`. This is used to keep loading data that was saved under an
+    /// old name of `T`, such as its type name before it was moved to another module. Panics if `T` has not been
+    /// registered.
+    pub fn add_alias<T: 'static>(&mut self, alias: &str) {
+        let type_name = std::any::type_name::<T>();
+        if !self.registrations.contains_key(type_name) {
+            panic!(
+                "Type {} must be registered before it is given an alias",
+                type_name
+            );
+        }
+        self.names.insert(alias.to_string(), type_name.to_string());
+    }
+
+    /// Looks up a registration by its stable name, an alias, its short name or its full name
     pub fn get(&self, type_name: &str) -> Option<&PropertyTypeRegistration> {
-        if let Some(long_name) = self.short_names.get(type_name) {
+        if let Some(long_name) = self.names.get(type_name) {
+            self.registrations.get(long_name)
+        } else if let Some(long_name) = self.short_names.get(type_name) {
             self.registrations.get(long_name)
         } else {
             self.registrations.get(type_name)
         }
     }
 
+    /// Returns the name that `type_name` is serialized with: its stable name if it has one, otherwise its short name
+    /// if that is unambiguous, otherwise its full name
     pub fn format_type_name(&self, type_name: &str) -> Option<&str> {
         self.get(type_name).map(|registration| {
-            if self.short_names.contains_key(&registration.short_name) {
+            if let Some(stable_name) = &registration.stable_name {
+                stable_name
+            } else if self.short_names.contains_key(&registration.short_name) {
                 &registration.short_name
             } else {
                 registration.name
@@ -70,6 +111,10 @@ pub struct PropertyTypeRegistration {
     ) -> Result<Box<dyn Property>, erased_serde::Error>,
     pub short_name: String,
     pub name: &'static str,
+    /// The name used instead of `name` when serializing. See [PropertyTypeRegistry::set_stable_name].
+    pub stable_name: Option<String>,
+    /// The attributes of the fields of the type, if it derives [Properties](crate::Properties)
+    pub fields: Vec<FieldAttributes>,
 }
 
 impl fmt::Debug for PropertyTypeRegistration {
@@ -86,6 +131,8 @@ impl fmt::Debug for PropertyTypeRegistration {
             )
             .field("short_name", &self.short_name)
             .field("name", &self.name)
+            .field("stable_name", &self.stable_name)
+            .field("fields", &self.fields)
             .finish()
     }
 }
@@ -103,9 +150,16 @@ impl PropertyTypeRegistration {
                 },
             name: type_name,
             short_name: Self::get_short_name(type_name),
+            stable_name: None,
+            fields: T::field_attributes(),
         }
     }
 
+    /// Returns the attributes of the field with the given name
+    pub fn field(&self, name: &str) -> Option<&FieldAttributes> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
     pub fn get_short_name(full_name: &str) -> String {
         let mut short_name = String::new();
 
@@ -159,9 +213,31 @@ impl PropertyTypeRegistration {
 
 #[cfg(test)]
 mod test {
-    use crate::PropertyTypeRegistration;
+    use crate::{PropertyTypeRegistration, PropertyTypeRegistry};
     use std::collections::HashMap;
 
+    #[test]
+    fn stable_names_and_aliases() {
+        let mut registry = PropertyTypeRegistry::default();
+        registry.register::<Option<f64>>();
+        let type_name = std::any::type_name::<Option<f64>>();
+        assert_eq!(registry.format_type_name(type_name), Some("Option<f64>"));
+
+        registry.set_stable_name::<Option<f64>>("maybe_number");
+        registry.add_alias::<Option<f64>>("old::MaybeNumber");
+        assert_eq!(registry.format_type_name(type_name), Some("maybe_number"));
+        for name in &["maybe_number", "old::MaybeNumber", "Option<f64>", type_name] {
+            assert_eq!(registry.get(name).unwrap().name, type_name);
+        }
+
+        registry.set_stable_name::<Option<f64>>("optional_number");
+        assert!(registry.get("maybe_number").is_none());
+        assert_eq!(
+            registry.format_type_name("old::MaybeNumber"),
+            Some("optional_number")
+        );
+    }
+
     #[test]
     fn test_get_short_name() {
         assert_eq!(
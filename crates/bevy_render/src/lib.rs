@@ -100,6 +100,7 @@ impl Plugin for RenderPlugin {
             .add_stage_after(stage::RENDER, stage::POST_RENDER)
             .add_asset::<Mesh>()
             .add_asset::<Texture>()
+            .register_asset_byte_size::<Texture>()
             .add_asset::<Shader>()
             .add_asset::<PipelineDescriptor>()
             .register_component::<Camera>()
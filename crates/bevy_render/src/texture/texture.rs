@@ -3,7 +3,7 @@ use crate::renderer::{
     RenderResource, RenderResourceContext, RenderResourceId, RenderResourceType,
 };
 use bevy_app::prelude::{EventReader, Events};
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetByteSize, AssetEvent, Assets, Handle};
 use bevy_ecs::{Res, ResMut};
 use bevy_math::Vec2;
 use bevy_type_registry::TypeUuid;
@@ -30,6 +30,12 @@ impl Default for Texture {
     }
 }
 
+impl AssetByteSize for Texture {
+    fn byte_size(&self) -> usize {
+        self.data.len()
+    }
+}
+
 impl Texture {
     pub fn new(size: Vec2, data: Vec<u8>, format: TextureFormat) -> Self {
         debug_assert_eq!(
@@ -1,25 +1,91 @@
 use bevy_asset::Handle;
-use bevy_ecs::{Command, Commands, Resources, World};
+use bevy_ecs::{Command, Commands, Entity, Resources, World};
 
-use crate::{Scene, SceneSpawner};
+use crate::{DynamicScene, InstanceId, Scene, SceneSpawner};
 
 pub struct SpawnScene {
     scene_handle: Handle<Scene>,
+    instance_id: InstanceId,
+    parent: Option<Entity>,
 }
 
 impl Command for SpawnScene {
     fn write(self: Box<Self>, _world: &mut World, resources: &mut Resources) {
         let mut spawner = resources.get_mut::<SceneSpawner>().unwrap();
-        spawner.spawn(self.scene_handle);
+        spawner.spawn_with_id(self.scene_handle, self.instance_id, self.parent);
     }
 }
 
+pub struct SpawnDynamicScene {
+    scene_handle: Handle<DynamicScene>,
+    instance_id: InstanceId,
+    parent: Option<Entity>,
+}
+
+impl Command for SpawnDynamicScene {
+    fn write(self: Box<Self>, _world: &mut World, resources: &mut Resources) {
+        let mut spawner = resources.get_mut::<SceneSpawner>().unwrap();
+        spawner.spawn_dynamic_with_id(self.scene_handle, self.instance_id, self.parent);
+    }
+}
+
+/// Spawns scenes with [SceneSpawner]. The returned [InstanceId]s can be used to despawn the instances, and are sent in
+/// [SceneInstanceReady](crate::SceneInstanceReady) events once the instances are spawned.
 pub trait SpawnSceneCommands {
-    fn spawn_scene(&mut self, scene: Handle<Scene>) -> &mut Self;
+    fn spawn_scene(&mut self, scene: Handle<Scene>) -> InstanceId;
+    /// Spawns the scene with its root entities as children of `parent`. See [SceneSpawner::spawn_as_child].
+    fn spawn_scene_as_child(&mut self, scene: Handle<Scene>, parent: Entity) -> InstanceId;
+    fn spawn_dynamic_scene(&mut self, scene: Handle<DynamicScene>) -> InstanceId;
+    /// Spawns the scene with its root entities as children of `parent`. See [SceneSpawner::spawn_dynamic_as_child].
+    fn spawn_dynamic_scene_as_child(
+        &mut self,
+        scene: Handle<DynamicScene>,
+        parent: Entity,
+    ) -> InstanceId;
 }
 
 impl SpawnSceneCommands for Commands {
-    fn spawn_scene(&mut self, scene_handle: Handle<Scene>) -> &mut Self {
-        self.add_command(SpawnScene { scene_handle })
+    fn spawn_scene(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.add_command(SpawnScene {
+            scene_handle,
+            instance_id,
+            parent: None,
+        });
+        instance_id
+    }
+
+    fn spawn_scene_as_child(&mut self, scene_handle: Handle<Scene>, parent: Entity) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.add_command(SpawnScene {
+            scene_handle,
+            instance_id,
+            parent: Some(parent),
+        });
+        instance_id
+    }
+
+    fn spawn_dynamic_scene(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.add_command(SpawnDynamicScene {
+            scene_handle,
+            instance_id,
+            parent: None,
+        });
+        instance_id
+    }
+
+    fn spawn_dynamic_scene_as_child(
+        &mut self,
+        scene_handle: Handle<DynamicScene>,
+        parent: Entity,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.add_command(SpawnDynamicScene {
+            scene_handle,
+            instance_id,
+            parent: Some(parent),
+        });
+        instance_id
     }
 }
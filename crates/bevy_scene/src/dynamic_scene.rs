@@ -1,21 +1,38 @@
-use crate::{serde::SceneSerializer, Scene};
+use crate::{
+    serde::{SceneDeserializer, SceneSerializer},
+    Scene,
+};
 use anyhow::Result;
+use bevy_asset::{AssetPath, Handle};
 use bevy_ecs::{EntityMap, Resources, World};
-use bevy_property::{DynamicProperties, PropertyTypeRegistry};
+use bevy_property::{
+    DynamicProperties, Properties, PropertyTypeRegistration, PropertyTypeRegistry,
+};
 use bevy_type_registry::{ComponentRegistry, TypeRegistry, TypeUuid};
-use serde::Serialize;
+use bincode::Options;
+use serde::{de::DeserializeSeed, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum DynamicSceneToWorldError {
     #[error("Scene contains an unregistered component.")]
     UnregisteredComponent { type_name: String },
+    #[error("Scene contains an unregistered resource.")]
+    UnregisteredResource { type_name: String },
+    #[error("Scene contains a resource that has not been inserted.")]
+    MissingResource { type_name: String },
 }
 
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
     pub entities: Vec<Entity>,
+    /// Other scenes nested in this one, such as prefabs placed in a level. Instances are spawned by
+    /// [SceneSpawner](crate::SceneSpawner).
+    pub instances: Vec<SceneInstance>,
+    /// Resources registered with [ResourceRegistry](bevy_type_registry::ResourceRegistry). [SceneSpawner](crate::SceneSpawner)
+    /// applies them once, when the scene is spawned; they are ignored when the scene is nested in another scene.
+    pub resources: Vec<DynamicProperties>,
 }
 
 pub struct Entity {
@@ -23,38 +40,171 @@ pub struct Entity {
     pub components: Vec<DynamicProperties>,
 }
 
+impl Clone for Entity {
+    fn clone(&self) -> Self {
+        Entity {
+            entity: self.entity,
+            components: clone_properties(&self.components),
+        }
+    }
+}
+
+fn clone_properties(properties: &[DynamicProperties]) -> Vec<DynamicProperties> {
+    properties
+        .iter()
+        .map(|properties| properties.to_dynamic())
+        .collect()
+}
+
+/// An instance of another [DynamicScene] nested in a scene, with property overrides that only apply to this instance
+pub struct SceneInstance {
+    /// The path of the nested scene, as written in the scene file
+    pub scene: AssetPath<'static>,
+    /// The nested scene. Scene loaders resolve `scene` against the asset source of the containing scene and store a
+    /// strong handle here, so that nested scenes stay loaded for as long as the scenes that contain them.
+    pub handle: Handle<DynamicScene>,
+    /// Components to change on (or add to) the nested scene's entities. `entity` refers to an entity id in the nested
+    /// scene, and each component only needs the properties it overrides.
+    pub overrides: Vec<Entity>,
+}
+
+impl Clone for SceneInstance {
+    fn clone(&self) -> Self {
+        SceneInstance {
+            scene: self.scene.clone(),
+            handle: self.handle.clone(),
+            overrides: self.overrides.clone(),
+        }
+    }
+}
+
+impl SceneInstance {
+    /// Creates an instance of the scene at the given path. The instance only holds a weak handle to the scene, which
+    /// must be loaded separately.
+    pub fn new<'a, P: Into<AssetPath<'a>>>(scene: P) -> Self {
+        let scene = scene.into().to_owned();
+        SceneInstance {
+            handle: Handle::weak(scene.get_id().into()),
+            scene,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Overrides the properties of a component on the entity with the given id in the nested scene
+    pub fn with_override(mut self, entity: u32, component: DynamicProperties) -> Self {
+        if let Some(entity_override) = self
+            .overrides
+            .iter_mut()
+            .find(|entity_override| entity_override.entity == entity)
+        {
+            entity_override.components.push(component);
+        } else {
+            self.overrides.push(Entity {
+                entity,
+                components: vec![component],
+            });
+        }
+        self
+    }
+}
+
+impl Clone for DynamicScene {
+    fn clone(&self) -> Self {
+        DynamicScene {
+            entities: self.entities.clone(),
+            instances: self.instances.clone(),
+            resources: clone_properties(&self.resources),
+        }
+    }
+}
+
 impl DynamicScene {
     pub fn from_scene(scene: &Scene, component_registry: &ComponentRegistry) -> Self {
-        Self::from_world(&scene.world, component_registry)
+        let mut dynamic_scene = DynamicScene::default();
+        dynamic_scene.add_entities_from_world(&scene.world, component_registry, |_, _| true);
+        dynamic_scene
+    }
+
+    /// Captures the registered components of every entity in the world, and the registered resources
+    pub fn from_world(
+        world: &World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+    ) -> Self {
+        Self::from_world_filtered(world, resources, component_registry, |_, _| true)
     }
 
-    pub fn from_world(world: &World, component_registry: &ComponentRegistry) -> Self {
+    /// Captures the registered components of the entities in the world for which `filter` returns true, and the
+    /// registered resources
+    pub fn from_world_filtered(
+        world: &World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+        filter: impl Fn(&World, bevy_ecs::Entity) -> bool,
+    ) -> Self {
         let mut scene = DynamicScene::default();
+        scene.add_entities_from_world(world, component_registry, filter);
+        for resource_registration in component_registry.resources.iter() {
+            if let Some(properties) = resource_registration.get_resource_properties(resources) {
+                scene.resources.push(properties);
+            }
+        }
+
+        scene
+    }
+
+    fn add_entities_from_world(
+        &mut self,
+        world: &World,
+        component_registry: &ComponentRegistry,
+        filter: impl Fn(&World, bevy_ecs::Entity) -> bool,
+    ) {
         for archetype in world.archetypes() {
-            let mut entities = Vec::new();
             for (index, entity) in archetype.iter_entities().enumerate() {
-                if index == entities.len() {
-                    entities.push(Entity {
-                        entity: entity.id(),
-                        components: Vec::new(),
-                    })
+                if !filter(world, *entity) {
+                    continue;
                 }
+
+                let mut scene_entity = Entity {
+                    entity: entity.id(),
+                    components: Vec::new(),
+                };
                 for type_info in archetype.types() {
                     if let Some(component_registration) = component_registry.get(&type_info.id()) {
                         let properties =
                             component_registration.get_component_properties(&archetype, index);
 
-                        entities[index].components.push(properties.to_dynamic());
+                        scene_entity.components.push(properties.to_dynamic());
                     }
                 }
+                self.entities.push(scene_entity);
             }
-
-            scene.entities.extend(entities.drain(..));
         }
+    }
 
-        scene
+    /// Returns every component, resource, and instance override with the given type name. Scene files use short type
+    /// names unless they are ambiguous, so a short name also matches the full names it is short for. This is mostly
+    /// useful for migrating scenes saved by older versions of a game.
+    pub fn properties_with_type_name_mut<'a>(
+        &'a mut self,
+        type_name: &'a str,
+    ) -> impl Iterator<Item = &'a mut DynamicProperties> + 'a {
+        let components = self
+            .entities
+            .iter_mut()
+            .chain(
+                self.instances
+                    .iter_mut()
+                    .flat_map(|instance| instance.overrides.iter_mut()),
+            )
+            .flat_map(|entity| entity.components.iter_mut());
+        components
+            .chain(self.resources.iter_mut())
+            .filter(move |properties| type_names_match(&properties.type_name, type_name))
     }
 
+    /// Writes the entities of this scene to the given world, and applies its resources to the existing resources.
+    /// Nested [SceneInstance]s are not written; use [SceneSpawner](crate::SceneSpawner) to spawn scenes with instances.
     pub fn write_to_world(
         &self,
         world: &mut World,
@@ -62,6 +212,16 @@ impl DynamicScene {
     ) -> Result<(), DynamicSceneToWorldError> {
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
+        self.write_entities_to_world(world, resources, &component_registry)?;
+        write_resources(&self.resources, resources, &component_registry)
+    }
+
+    fn write_entities_to_world(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+    ) -> Result<(), DynamicSceneToWorldError> {
         let mut entity_map = EntityMap::default();
         for scene_entity in self.entities.iter() {
             let new_entity = world.reserve_entity();
@@ -90,18 +250,75 @@ impl DynamicScene {
         Ok(())
     }
 
-    // TODO: move to AssetSaver when it is implemented
     pub fn serialize_ron(&self, registry: &PropertyTypeRegistry) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
 
+    /// Serializes this scene in the compact binary `.scnb` format
+    pub fn serialize_binary(
+        &self,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<Vec<u8>, bincode::Error> {
+        serialize_binary(SceneSerializer::new(self, registry))
+    }
+
+    /// Serializes this scene in the JSON `.scn.json` format
+    pub fn serialize_json(
+        &self,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<String, serde_json::Error> {
+        serialize_json(SceneSerializer::new(self, registry))
+    }
+
+    /// Serializes this scene in the YAML `.scn.yaml` format
+    pub fn serialize_yaml(
+        &self,
+        registry: &PropertyTypeRegistry,
+    ) -> Result<String, serde_yaml::Error> {
+        serialize_yaml(SceneSerializer::new(self, registry))
+    }
+
+    /// Converts this scene to a [Scene]. Resources are not included, as a [Scene] only has a [World].
     pub fn get_scene(&self, resources: &Resources) -> Result<Scene, DynamicSceneToWorldError> {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let component_registry = type_registry.component.read();
         let mut world = World::default();
-        self.write_to_world(&mut world, resources)?;
+        self.write_entities_to_world(&mut world, resources, &component_registry)?;
         Ok(Scene::new(world))
     }
 }
 
+fn type_names_match(a: &str, b: &str) -> bool {
+    let is_short = |name: &str| !name.contains("::");
+    a == b
+        || (is_short(a) != is_short(b)
+            && PropertyTypeRegistration::get_short_name(a)
+                == PropertyTypeRegistration::get_short_name(b))
+}
+
+/// Applies the given resource properties to the existing resources
+pub(crate) fn write_resources(
+    scene_resources: &[DynamicProperties],
+    resources: &Resources,
+    component_registry: &ComponentRegistry,
+) -> Result<(), DynamicSceneToWorldError> {
+    for resource in scene_resources.iter() {
+        let resource_registration = component_registry
+            .resources
+            .get_with_name(&resource.type_name)
+            .ok_or_else(|| DynamicSceneToWorldError::UnregisteredResource {
+                type_name: resource.type_name.to_string(),
+            })?;
+        if !resource_registration.apply_property_to_resource(resources, resource) {
+            return Err(DynamicSceneToWorldError::MissingResource {
+                type_name: resource.type_name.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
 pub fn serialize_ron<S>(serialize: S) -> Result<String, ron::Error>
 where
     S: Serialize,
@@ -115,3 +332,400 @@ where
     serialize.serialize(&mut ron_serializer)?;
     Ok(String::from_utf8(buf).unwrap())
 }
+
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, bincode::Error>
+where
+    S: Serialize,
+{
+    bincode::DefaultOptions::new().serialize(&serialize)
+}
+
+pub fn deserialize_binary<'de, D>(
+    deserialize: D,
+    bytes: &'de [u8],
+) -> Result<D::Value, bincode::Error>
+where
+    D: DeserializeSeed<'de>,
+{
+    bincode::DefaultOptions::new().deserialize_seed(deserialize, bytes)
+}
+
+pub fn serialize_json<S>(serialize: S) -> Result<String, serde_json::Error>
+where
+    S: Serialize,
+{
+    serde_json::to_string_pretty(&serialize)
+}
+
+pub fn deserialize_json<'de, D>(
+    deserialize: D,
+    bytes: &'de [u8],
+) -> Result<D::Value, serde_json::Error>
+where
+    D: DeserializeSeed<'de>,
+{
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = deserialize.deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+pub fn serialize_yaml<S>(serialize: S) -> Result<String, serde_yaml::Error>
+where
+    S: Serialize,
+{
+    serde_yaml::to_string(&serialize)
+}
+
+pub fn deserialize_yaml<'de, D>(
+    deserialize: D,
+    bytes: &'de [u8],
+) -> Result<D::Value, serde_yaml::Error>
+where
+    D: DeserializeSeed<'de>,
+{
+    deserialize.deserialize(serde_yaml::Deserializer::from_slice(bytes))
+}
+
+/// Converts a RON `.scn` scene to the binary `.scnb` format
+pub fn scene_ron_to_binary(ron: &[u8], registry: &PropertyTypeRegistry) -> Result<Vec<u8>> {
+    let mut deserializer = ron::de::Deserializer::from_bytes(ron)?;
+    let scene = SceneDeserializer {
+        property_type_registry: registry,
+    }
+    .deserialize(&mut deserializer)?;
+    Ok(scene.serialize_binary(registry)?)
+}
+
+/// Converts a binary `.scnb` scene to the RON `.scn` format
+pub fn scene_binary_to_ron(bytes: &[u8], registry: &PropertyTypeRegistry) -> Result<String> {
+    let scene = deserialize_binary(
+        SceneDeserializer {
+            property_type_registry: registry,
+        },
+        bytes,
+    )?;
+    Ok(scene.serialize_ron(registry)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        deserialize_binary, deserialize_json, deserialize_yaml, scene_binary_to_ron,
+        scene_ron_to_binary, DynamicScene,
+    };
+    use crate::serde::SceneDeserializer;
+    use bevy_ecs::{Component, FromResources, Resources, World};
+    use bevy_property::{DeserializeProperty, Properties, PropertiesVal, PropertyTypeRegistry};
+    use bevy_type_registry::{TypeRegistry, TypeUuid};
+    use serde::de::DeserializeSeed;
+
+    fn register_component<T>(type_registry: &TypeRegistry)
+    where
+        T: Properties + Component + FromResources + DeserializeProperty,
+    {
+        type_registry.component.write().register::<T>();
+        type_registry.property.write().register::<T>();
+    }
+
+    fn from_ron(ron: &str, property_type_registry: &PropertyTypeRegistry) -> DynamicScene {
+        let mut deserializer = ron::de::Deserializer::from_str(ron).unwrap();
+        SceneDeserializer {
+            property_type_registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    /// Captures the world in a scene, then writes the scene as RON and reads it back
+    fn ron_round_trip(
+        world: &World,
+        resources: &Resources,
+        type_registry: &TypeRegistry,
+    ) -> DynamicScene {
+        let scene = DynamicScene::from_world(world, resources, &type_registry.component.read());
+        let property_type_registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&property_type_registry).unwrap();
+        from_ron(&ron, &property_type_registry)
+    }
+
+    /// Writes the scene to a new world
+    fn load_world(scene: &DynamicScene, type_registry: &TypeRegistry) -> World {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        resources.insert(type_registry.clone());
+        scene.write_to_world(&mut world, &resources).unwrap();
+        world
+    }
+
+    #[derive(Properties, Default)]
+    struct Score {
+        points: u32,
+        seed: u64,
+    }
+
+    #[test]
+    fn registered_resources_round_trip() {
+        let type_registry = TypeRegistry::default();
+        type_registry
+            .component
+            .write()
+            .resources
+            .register::<Score>();
+        type_registry.property.write().register::<Score>();
+        let mut resources = Resources::default();
+        resources.insert(type_registry.clone());
+        resources.insert(Score {
+            points: 42,
+            seed: 7,
+        });
+
+        let scene = ron_round_trip(&World::default(), &resources, &type_registry);
+        assert_eq!(scene.resources.len(), 1);
+
+        *resources.get_mut::<Score>().unwrap() = Score::default();
+        scene
+            .write_to_world(&mut World::default(), &resources)
+            .unwrap();
+        let score = resources.get::<Score>().unwrap();
+        assert_eq!((score.points, score.seed), (42, 7));
+    }
+
+    #[derive(Properties, Debug, PartialEq)]
+    enum AiState {
+        Idle,
+        Fleeing(String),
+        Chasing {
+            target: String,
+            #[property(ignore)]
+            ticks: u32,
+        },
+    }
+
+    impl Default for AiState {
+        fn default() -> Self {
+            AiState::Idle
+        }
+    }
+
+    #[test]
+    fn enum_components_round_trip() {
+        let type_registry = TypeRegistry::default();
+        register_component::<AiState>(&type_registry);
+        let mut world = World::default();
+        world.spawn((AiState::Fleeing("wolf".to_string()),));
+        world.spawn((AiState::Chasing {
+            target: "player".to_string(),
+            ticks: 3,
+        },));
+        world.spawn((AiState::Idle,));
+
+        let scene = ron_round_trip(&world, &Resources::default(), &type_registry);
+        let loaded_world = load_world(&scene, &type_registry);
+        let mut states = loaded_world
+            .query::<&AiState>()
+            .iter()
+            .map(|state| format!("{:?}", *state))
+            .collect::<Vec<_>>();
+        states.sort();
+        assert_eq!(
+            states,
+            vec![
+                "Chasing { target: \"player\", ticks: 0 }",
+                "Fleeing(\"wolf\")",
+                "Idle",
+            ]
+        );
+    }
+
+    #[test]
+    fn binary_scenes_round_trip() {
+        let type_registry = TypeRegistry::default();
+        register_component::<AiState>(&type_registry);
+        let mut world = World::default();
+        world.spawn((AiState::Chasing {
+            target: "player".to_string(),
+            ticks: 3,
+        },));
+        world.spawn((AiState::Fleeing("wolf".to_string()),));
+
+        let scene = DynamicScene::from_world(
+            &world,
+            &Resources::default(),
+            &type_registry.component.read(),
+        );
+        let property_type_registry = type_registry.property.read();
+        let bytes = scene.serialize_binary(&property_type_registry).unwrap();
+        let ron = scene.serialize_ron(&property_type_registry).unwrap();
+        assert!(bytes.len() < ron.len());
+
+        let loaded = deserialize_binary(
+            SceneDeserializer {
+                property_type_registry: &property_type_registry,
+            },
+            &bytes,
+        )
+        .unwrap();
+        assert_eq!(loaded.serialize_ron(&property_type_registry).unwrap(), ron);
+
+        let binary = scene_ron_to_binary(ron.as_bytes(), &property_type_registry).unwrap();
+        assert_eq!(binary, bytes);
+        assert_eq!(
+            scene_binary_to_ron(&binary, &property_type_registry).unwrap(),
+            ron
+        );
+        let loaded_world = load_world(&loaded, &type_registry);
+        assert_eq!(loaded_world.query::<&AiState>().iter().count(), 2);
+    }
+
+    #[derive(Properties, Default, TypeUuid)]
+    #[uuid = "0d4b5bd5-2c60-4bd1-8aa4-2a0e1fbd8e2b"]
+    struct Door {
+        open: bool,
+    }
+
+    #[test]
+    fn stable_type_names_round_trip() {
+        let type_registry = TypeRegistry::default();
+        register_component::<Door>(&type_registry);
+        {
+            let mut property_type_registry = type_registry.property.write();
+            property_type_registry.set_stable_name::<Door>(&Door::TYPE_UUID.to_string());
+            property_type_registry.add_alias::<Door>("old_level::Door");
+        }
+        let mut world = World::default();
+        world.spawn((Door { open: true },));
+
+        let scene = DynamicScene::from_world(
+            &world,
+            &Resources::default(),
+            &type_registry.component.read(),
+        );
+        let property_type_registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&property_type_registry).unwrap();
+        assert!(ron.contains("0d4b5bd5-2c60-4bd1-8aa4-2a0e1fbd8e2b"));
+        assert!(!ron.contains("Door"));
+
+        let old_ron = ron.replace("0d4b5bd5-2c60-4bd1-8aa4-2a0e1fbd8e2b", "old_level::Door");
+        for ron in &[ron, old_ron] {
+            let loaded_world = load_world(&from_ron(ron, &property_type_registry), &type_registry);
+            let doors = loaded_world
+                .query::<&Door>()
+                .iter()
+                .map(|door| door.open)
+                .collect::<Vec<_>>();
+            assert_eq!(doors, vec![true]);
+        }
+    }
+
+    #[derive(Properties, Default)]
+    struct Turret {
+        /// Hit points of a new turret
+        #[property(default = 100.0, range = 0.0..=1000.0, step = 10.0)]
+        health: f32,
+        #[property(rename = "fire_rate", tooltip = "Shots per second")]
+        rate: f32,
+        name: String,
+    }
+
+    #[test]
+    fn missing_fields_use_attribute_defaults() {
+        let type_registry = TypeRegistry::default();
+        register_component::<Turret>(&type_registry);
+        let property_type_registry = type_registry.property.read();
+
+        let registration = property_type_registry.get("Turret").unwrap();
+        let health = registration.field("health").unwrap();
+        assert_eq!(health.range, Some(0.0..=1000.0));
+        assert_eq!(health.step, Some(10.0));
+        assert_eq!(health.tooltip, Some("Hit points of a new turret"));
+        let rate = registration.field("fire_rate").unwrap();
+        assert!(rate.default.is_none());
+        assert_eq!(rate.tooltip, Some("Shots per second"));
+        assert!(registration.field("rate").is_none());
+
+        let ron = r#"[
+            (
+                entity: 0,
+                components: [
+                    {
+                        "type": "Turret",
+                        "map": {
+                            "fire_rate": 2.0,
+                            "name": "east gate",
+                        },
+                    },
+                ],
+            ),
+        ]"#;
+        let scene = from_ron(ron, &property_type_registry);
+        let component = &scene.entities[0].components[0];
+        assert_eq!(*component.prop_val::<f32>("health").unwrap(), 100.0);
+
+        let world = load_world(&scene, &type_registry);
+        let turrets = world
+            .query::<&Turret>()
+            .iter()
+            .map(|turret| (turret.health, turret.rate, turret.name.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(turrets, vec![(100.0, 2.0, "east gate".to_string())]);
+    }
+
+    #[test]
+    fn text_formats_produce_the_same_scene() {
+        let type_registry = TypeRegistry::default();
+        register_component::<AiState>(&type_registry);
+        register_component::<Turret>(&type_registry);
+        type_registry
+            .component
+            .write()
+            .resources
+            .register::<Score>();
+        type_registry.property.write().register::<Score>();
+        let mut resources = Resources::default();
+        resources.insert(Score {
+            points: 42,
+            seed: u64::MAX,
+        });
+        let mut world = World::default();
+        world.spawn((
+            AiState::Chasing {
+                target: "player".to_string(),
+                ticks: 0,
+            },
+            Turret {
+                health: 75.5,
+                rate: 2.0,
+                name: "east gate".to_string(),
+            },
+        ));
+        world.spawn((AiState::Fleeing("wolf".to_string()),));
+        world.spawn((AiState::Idle,));
+
+        let scene = DynamicScene::from_world(&world, &resources, &type_registry.component.read());
+        let property_type_registry = type_registry.property.read();
+        let ron = scene.serialize_ron(&property_type_registry).unwrap();
+        let json = scene.serialize_json(&property_type_registry).unwrap();
+        let yaml = scene.serialize_yaml(&property_type_registry).unwrap();
+
+        let scene_deserializer = || SceneDeserializer {
+            property_type_registry: &property_type_registry,
+        };
+        let scenes = [
+            from_ron(&ron, &property_type_registry),
+            deserialize_json(scene_deserializer(), json.as_bytes()).unwrap(),
+            deserialize_yaml(scene_deserializer(), yaml.as_bytes()).unwrap(),
+        ];
+        for loaded in scenes.iter() {
+            assert_eq!(loaded.serialize_ron(&property_type_registry).unwrap(), ron);
+            assert_eq!(
+                loaded.serialize_json(&property_type_registry).unwrap(),
+                json
+            );
+            assert_eq!(
+                loaded.serialize_yaml(&property_type_registry).unwrap(),
+                yaml
+            );
+        }
+    }
+}
@@ -1,23 +1,35 @@
 mod command;
 mod dynamic_scene;
+mod save_game;
 mod scene;
+mod scene_entity;
+mod scene_format;
 mod scene_loader;
+mod scene_saver;
 mod scene_spawner;
 pub mod serde;
 
 pub use command::*;
 pub use dynamic_scene::*;
+pub use save_game::*;
 pub use scene::*;
+pub use scene_entity::*;
+pub use scene_format::*;
 pub use scene_loader::*;
+pub use scene_saver::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
-    pub use crate::{DynamicScene, Scene, SceneSpawner, SpawnSceneCommands};
+    pub use crate::{
+        AddSaveGameMigration, DynamicScene, SaveGame, Scene, SceneEntityId, SceneEntityRef,
+        SceneSpawner, SpawnSceneCommands,
+    };
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::AddAsset;
 use bevy_ecs::IntoThreadLocalSystem;
+use bevy_type_registry::RegisterType;
 
 #[derive(Default)]
 pub struct ScenePlugin;
@@ -26,11 +38,46 @@ pub const SCENE_STAGE: &str = "scene";
 
 impl Plugin for ScenePlugin {
     fn build(&self, app: &mut AppBuilder) {
+        // migrations may have been added before this plugin
+        app.resources_mut()
+            .get_or_insert_with(SaveGameRegistry::default);
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_saver::<SceneSaver>()
+            .init_asset_loader::<BinarySceneLoader>()
+            .init_asset_saver::<BinarySceneSaver>()
+            .init_asset_loader::<JsonSceneLoader>()
+            .init_asset_saver::<JsonSceneSaver>()
+            .init_asset_loader::<YamlSceneLoader>()
+            .init_asset_saver::<YamlSceneSaver>()
+            .add_asset::<SaveGame>()
+            .init_asset_loader::<SaveGameLoader>()
+            .init_asset_saver::<SaveGameSaver>()
+            .register_component::<SceneEntityId>()
+            .register_property::<SceneEntityRef>()
             .init_resource::<SceneSpawner>()
+            .add_event::<SceneInstanceReady>()
             .add_stage_after(stage::EVENT, SCENE_STAGE)
             .add_system_to_stage(SCENE_STAGE, scene_spawner_system.thread_local_system());
     }
 }
+
+/// An app with the asset stages, an [AssetServer](bevy_asset::AssetServer) and the [DynamicScene] asset, for tests
+#[cfg(test)]
+pub(crate) fn test_app() -> AppBuilder {
+    use bevy_asset::{AssetDiagnostics, AssetServer, MemoryAssetIo};
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+    use bevy_type_registry::TypeRegistry;
+
+    let mut app_builder = App::build();
+    app_builder
+        .add_stage(bevy_asset::stage::LOAD_ASSETS)
+        .add_stage(bevy_asset::stage::ASSET_EVENTS)
+        .init_resource::<TypeRegistry>()
+        .init_resource::<AssetDiagnostics>()
+        .add_resource(ComputeTaskPool(TaskPool::new()))
+        .add_resource(AssetServer::new(MemoryAssetIo::new(), TaskPool::new()))
+        .add_asset::<DynamicScene>();
+    app_builder
+}
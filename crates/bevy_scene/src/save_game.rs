@@ -0,0 +1,254 @@
+use crate::{DynamicScene, DynamicSceneToWorldError};
+use bevy_app::AppBuilder;
+use bevy_ecs::{Component, Resources, World};
+use bevy_type_registry::{TypeRegistry, TypeUuid};
+use bevy_utils::HashMap;
+use parking_lot::RwLock;
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Upgrades the scene of a save game from one version to the next, usually by editing its
+/// [DynamicProperties](bevy_property::DynamicProperties) to match renamed components and fields
+pub type SaveGameMigration = fn(&mut DynamicScene);
+
+#[derive(Error, Debug)]
+pub enum SaveGameError {
+    #[error("Save game version {version} is newer than the current version {current_version}.")]
+    UnsupportedVersion { version: u32, current_version: u32 },
+    #[error("There is no migration from save game version {version}.")]
+    MissingMigration { version: u32 },
+}
+
+/// A snapshot of game state, made of the entities chosen by a marker component or filter and the registered resources.
+///
+/// Save games are assets: add one to `Assets<SaveGame>` and save it with
+/// [AssetServer::save](bevy_asset::AssetServer::save) to a `.sav` path, which serializes and writes a copy of it on the
+/// asset server's task pool.
+/// Loading a `.sav` file runs the [SaveGameMigration]s needed to upgrade it to the current [SaveGameSchema] version.
+#[derive(Clone, TypeUuid)]
+#[uuid = "5bb46b1a-5e6c-4b8e-9a54-3c1c2d4e8f27"]
+pub struct SaveGame {
+    pub version: u32,
+    pub scene: DynamicScene,
+}
+
+impl SaveGame {
+    /// Captures the entities with a component of type `T`, and the registered resources. `T` should be registered too,
+    /// so that loaded entities keep it and are included in the next save.
+    pub fn from_world<T: Component>(world: &World, resources: &Resources) -> Self {
+        Self::from_world_filtered(world, resources, |world, entity| {
+            world.get::<T>(entity).is_ok()
+        })
+    }
+
+    /// Captures the entities for which `filter` returns true, and the registered resources
+    pub fn from_world_filtered(
+        world: &World,
+        resources: &Resources,
+        filter: impl Fn(&World, bevy_ecs::Entity) -> bool,
+    ) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let version = resources
+            .get::<SaveGameRegistry>()
+            .map_or(0, |registry| registry.schema.read().version());
+        let scene = DynamicScene::from_world_filtered(
+            world,
+            resources,
+            &type_registry.component.read(),
+            filter,
+        );
+        SaveGame { version, scene }
+    }
+
+    /// Spawns the saved entities in the given world and restores the saved resources
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        resources: &Resources,
+    ) -> Result<(), DynamicSceneToWorldError> {
+        self.scene.write_to_world(world, resources)
+    }
+}
+
+/// The current save game version, and the migrations that upgrade older save games to it
+#[derive(Debug, Default)]
+pub struct SaveGameSchema {
+    version: u32,
+    migrations: HashMap<u32, SaveGameMigration>,
+}
+
+impl SaveGameSchema {
+    /// The version written to new save games
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    /// Adds a migration that upgrades save games from `from_version` to `from_version + 1`. The current version is
+    /// raised to `from_version + 1` if it is lower.
+    pub fn add_migration(&mut self, from_version: u32, migration: SaveGameMigration) {
+        self.migrations.insert(from_version, migration);
+        self.version = self.version.max(from_version + 1);
+    }
+
+    /// Upgrades the given save game to the current version
+    pub fn migrate(&self, save_game: &mut SaveGame) -> Result<(), SaveGameError> {
+        if save_game.version > self.version {
+            return Err(SaveGameError::UnsupportedVersion {
+                version: save_game.version,
+                current_version: self.version,
+            });
+        }
+
+        while save_game.version < self.version {
+            let migration =
+                self.migrations
+                    .get(&save_game.version)
+                    .ok_or(SaveGameError::MissingMigration {
+                        version: save_game.version,
+                    })?;
+            migration(&mut save_game.scene);
+            save_game.version += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Shares the [SaveGameSchema] with the save game loader and saver
+#[derive(Debug, Clone, Default)]
+pub struct SaveGameRegistry {
+    pub schema: Arc<RwLock<SaveGameSchema>>,
+}
+
+pub trait AddSaveGameMigration {
+    fn add_save_game_migration(
+        &mut self,
+        from_version: u32,
+        migration: SaveGameMigration,
+    ) -> &mut Self;
+}
+
+impl AddSaveGameMigration for AppBuilder {
+    fn add_save_game_migration(
+        &mut self,
+        from_version: u32,
+        migration: SaveGameMigration,
+    ) -> &mut Self {
+        {
+            let registry = self
+                .resources_mut()
+                .get_or_insert_with(SaveGameRegistry::default);
+            registry
+                .schema
+                .write()
+                .add_migration(from_version, migration);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SaveGame, SaveGameError, SaveGameRegistry};
+    use crate::{
+        serde::{SaveGameDeserializer, SaveGameSerializer},
+        SaveGameSaver,
+    };
+    use bevy_asset::AssetSaver;
+    use bevy_ecs::{FromResources, Resources, World};
+    use bevy_property::{Properties, PropertyTypeRegistry};
+    use bevy_type_registry::TypeRegistry;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Properties, Default)]
+    struct Player {
+        hp: u32,
+    }
+
+    #[derive(Properties, Default)]
+    struct Tree {
+        height: f32,
+    }
+
+    fn serialize_round_trip(save_game: &SaveGame, registry: &PropertyTypeRegistry) -> SaveGame {
+        let ron = crate::serialize_ron(SaveGameSerializer {
+            save_game,
+            registry,
+        })
+        .unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        SaveGameDeserializer {
+            property_type_registry: registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap()
+    }
+
+    #[test]
+    fn saves_marked_entities_and_migrates_old_versions() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register::<Player>();
+        type_registry.component.write().register::<Tree>();
+        type_registry.property.write().register::<Player>();
+        resources.insert(type_registry);
+        resources.insert(SaveGameRegistry::default());
+        world.spawn((Player { hp: 3 },));
+        world.spawn((Tree { height: 2.0 },));
+
+        let save_game = SaveGame::from_world::<Player>(&world, &resources);
+        assert_eq!(save_game.version, 0);
+        assert_eq!(save_game.scene.entities.len(), 1);
+
+        let registry = resources.get::<SaveGameRegistry>().unwrap();
+        registry.schema.write().add_migration(0, |scene| {
+            for player in scene.properties_with_type_name_mut(std::any::type_name::<Player>()) {
+                player.rename("hp", "health");
+            }
+        });
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let mut loaded = serialize_round_trip(&save_game, &type_registry.property.read());
+        registry.schema.read().migrate(&mut loaded).unwrap();
+        assert_eq!(loaded.version, 1);
+        let player = &loaded.scene.entities[0].components[0];
+        assert!(player.prop("hp").is_none());
+        assert!(player.prop("health").is_some());
+
+        loaded.version = 2;
+        let result = registry.schema.read().migrate(&mut loaded);
+        assert!(matches!(
+            result,
+            Err(SaveGameError::UnsupportedVersion {
+                version: 2,
+                current_version: 1,
+            })
+        ));
+    }
+
+    #[test]
+    fn deferred_saves_serialize_a_copy() {
+        let mut world = World::default();
+        let mut resources = Resources::default();
+        let type_registry = TypeRegistry::default();
+        type_registry.component.write().register::<Player>();
+        type_registry.property.write().register::<Player>();
+        resources.insert(type_registry);
+        let player = world.spawn((Player { hp: 3 },));
+
+        let saver = SaveGameSaver::from_resources(&resources);
+        let path = std::path::Path::new("saves/slot_1.sav");
+        let mut save_game = SaveGame::from_world::<Player>(&world, &resources);
+        let expected = saver.save(&save_game, path).unwrap();
+        let save = saver.save_deferred(&save_game, path).unwrap();
+
+        world.get_mut::<Player>(player).unwrap().hp = 1;
+        save_game = SaveGame::from_world::<Player>(&world, &resources);
+        assert_ne!(saver.save(&save_game, path).unwrap(), expected);
+        assert_eq!(std::thread::spawn(save).join().unwrap().unwrap(), expected);
+    }
+}
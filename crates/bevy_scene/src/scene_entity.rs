@@ -0,0 +1,164 @@
+use bevy_ecs::{Entity, World};
+use bevy_property::{impl_property, Properties, Property};
+use bevy_type_registry::ComponentRegistry;
+use bevy_utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+
+/// A stable identifier for an entity in a scene, such as a GUID or a unique name. Entities in other scenes can refer to
+/// it with a [SceneEntityRef].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Properties)]
+pub struct SceneEntityId {
+    pub id: String,
+}
+
+impl SceneEntityId {
+    pub fn new(id: impl Into<String>) -> Self {
+        SceneEntityId { id: id.into() }
+    }
+}
+
+/// A reference to the entity with the given [SceneEntityId], which may be in another scene. References in components
+/// are resolved by [SceneSpawner](crate::SceneSpawner) when the scene containing them is spawned, or later when an
+/// entity with the id is spawned. Only the id is serialized.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SceneEntityRef {
+    pub id: String,
+    #[serde(skip)]
+    entity: Option<Entity>,
+}
+
+impl_property!(SceneEntityRef);
+
+impl SceneEntityRef {
+    pub fn new(id: impl Into<String>) -> Self {
+        SceneEntityRef {
+            id: id.into(),
+            entity: None,
+        }
+    }
+
+    /// The referenced entity, or `None` if the reference has not been resolved yet
+    pub fn entity(&self) -> Option<Entity> {
+        self.entity
+    }
+}
+
+/// A component of a spawned entity with [SceneEntityRef]s to ids that no spawned entity has. The references are
+/// resolved once entities with the ids are spawned. See
+/// [SceneSpawner::unresolved_entity_references](crate::SceneSpawner::unresolved_entity_references).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedEntityReferences {
+    pub entity: Entity,
+    /// The short name of the component type
+    pub component: String,
+    /// The ids that could not be resolved
+    pub ids: Vec<String>,
+    ty: TypeId,
+}
+
+/// Entities with a [SceneEntityId], by id
+#[derive(Debug, Default)]
+pub(crate) struct SceneEntities {
+    entities: HashMap<String, Entity>,
+    /// Components with [SceneEntityRef]s to ids that were not registered when they were resolved. They are resolved
+    /// again whenever new ids are added.
+    unresolved: Vec<UnresolvedEntityReferences>,
+}
+
+impl SceneEntities {
+    pub fn get(&self, id: &str) -> Option<Entity> {
+        self.entities.get(id).cloned()
+    }
+
+    /// Adds the given entities that have a [SceneEntityId]. If an id is already used, the newest entity wins.
+    pub fn add(&mut self, world: &World, entities: &[Entity]) {
+        for entity in entities.iter() {
+            if let Ok(scene_entity_id) = world.get::<SceneEntityId>(*entity) {
+                self.entities.insert(scene_entity_id.id.clone(), *entity);
+            }
+        }
+    }
+
+    pub fn remove(&mut self, entities: &[Entity]) {
+        self.entities.retain(|_, entity| !entities.contains(entity));
+        self.unresolved
+            .retain(|unresolved| !entities.contains(&unresolved.entity));
+    }
+
+    pub fn unresolved(&self) -> &[UnresolvedEntityReferences] {
+        &self.unresolved
+    }
+
+    /// Resolves the [SceneEntityRef]s in the given components, along with the components that could not be resolved
+    /// before. Components that still refer to unknown ids are kept, and resolved again next time.
+    pub fn resolve(
+        &mut self,
+        world: &mut World,
+        component_registry: &ComponentRegistry,
+        components: &[(Entity, TypeId)],
+    ) {
+        let unresolved = std::mem::take(&mut self.unresolved);
+        let retried = unresolved
+            .iter()
+            .map(|unresolved| (unresolved.entity, unresolved.ty))
+            .collect::<Vec<_>>();
+        for (entity, ty) in retried.iter().chain(components.iter()) {
+            let component_registration = match component_registry.get(ty) {
+                Some(component_registration) => component_registration,
+                None => continue,
+            };
+            let mut properties = match component_registration.get_entity_properties(world, *entity)
+            {
+                Some(properties) => properties.to_dynamic(),
+                None => continue,
+            };
+            let mut missing_ids = Vec::new();
+            self.resolve_property(&mut properties, &mut missing_ids);
+            component_registration.apply_property_to_entity(world, *entity, &properties);
+            if !missing_ids.is_empty() {
+                // only log references the first time they fail to resolve
+                if !retried.contains(&(*entity, *ty)) {
+                    log::warn!(
+                        "{} on {:?} refers to scene entities that have not been spawned yet: {:?}",
+                        component_registration.short_name,
+                        entity,
+                        missing_ids
+                    );
+                }
+                self.unresolved
+                    .retain(|unresolved| (unresolved.entity, unresolved.ty) != (*entity, *ty));
+                self.unresolved.push(UnresolvedEntityReferences {
+                    entity: *entity,
+                    component: component_registration.short_name.clone(),
+                    ids: missing_ids,
+                    ty: *ty,
+                });
+            }
+        }
+    }
+
+    fn resolve_property(&self, property: &mut dyn Property, missing_ids: &mut Vec<String>) {
+        if let Some(entity_ref) = property.any_mut().downcast_mut::<SceneEntityRef>() {
+            entity_ref.entity = self.get(&entity_ref.id);
+            if entity_ref.entity.is_none() {
+                missing_ids.push(entity_ref.id.clone());
+            }
+        } else if let Some(properties) = property.as_properties_mut() {
+            for index in 0..properties.prop_len() {
+                self.resolve_property(properties.prop_with_index_mut(index).unwrap(), missing_ids);
+            }
+        }
+    }
+}
+
+/// Returns true if the property is, or contains, a [SceneEntityRef]
+pub(crate) fn contains_refs(property: &dyn Property) -> bool {
+    if property.any().is::<SceneEntityRef>() {
+        true
+    } else if let Some(properties) = property.as_properties() {
+        properties.iter_props().any(contains_refs)
+    } else {
+        false
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    deserialize_binary, deserialize_json, deserialize_yaml, serde::SceneDeserializer, DynamicScene,
+};
+use anyhow::Result;
+use bevy_property::PropertyTypeRegistry;
+use serde::de::DeserializeSeed;
+
+/// A file format for [DynamicScene]s. [SceneFormatLoader](crate::SceneFormatLoader) and
+/// [SceneFormatSaver](crate::SceneFormatSaver) load and save scenes in any format.
+pub trait SceneFormat: Send + Sync + 'static {
+    /// The extensions of files in this format
+    const EXTENSIONS: &'static [&'static str];
+
+    fn deserialize(bytes: &[u8], registry: &PropertyTypeRegistry) -> Result<DynamicScene>;
+    fn serialize(scene: &DynamicScene, registry: &PropertyTypeRegistry) -> Result<Vec<u8>>;
+}
+
+/// The RON `.scn` format
+#[derive(Debug)]
+pub struct RonSceneFormat;
+
+impl SceneFormat for RonSceneFormat {
+    const EXTENSIONS: &'static [&'static str] = &["scn"];
+
+    fn deserialize(bytes: &[u8], registry: &PropertyTypeRegistry) -> Result<DynamicScene> {
+        let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+        let scene_deserializer = SceneDeserializer {
+            property_type_registry: registry,
+        };
+        Ok(scene_deserializer.deserialize(&mut deserializer)?)
+    }
+
+    fn serialize(scene: &DynamicScene, registry: &PropertyTypeRegistry) -> Result<Vec<u8>> {
+        Ok(scene.serialize_ron(registry)?.into_bytes())
+    }
+}
+
+/// The compact binary `.scnb` format
+#[derive(Debug)]
+pub struct BinarySceneFormat;
+
+impl SceneFormat for BinarySceneFormat {
+    const EXTENSIONS: &'static [&'static str] = &["scnb"];
+
+    fn deserialize(bytes: &[u8], registry: &PropertyTypeRegistry) -> Result<DynamicScene> {
+        let scene_deserializer = SceneDeserializer {
+            property_type_registry: registry,
+        };
+        Ok(deserialize_binary(scene_deserializer, bytes)?)
+    }
+
+    fn serialize(scene: &DynamicScene, registry: &PropertyTypeRegistry) -> Result<Vec<u8>> {
+        Ok(scene.serialize_binary(registry)?)
+    }
+}
+
+/// The JSON `.scn.json` format
+#[derive(Debug)]
+pub struct JsonSceneFormat;
+
+impl SceneFormat for JsonSceneFormat {
+    const EXTENSIONS: &'static [&'static str] = &["scn.json"];
+
+    fn deserialize(bytes: &[u8], registry: &PropertyTypeRegistry) -> Result<DynamicScene> {
+        let scene_deserializer = SceneDeserializer {
+            property_type_registry: registry,
+        };
+        Ok(deserialize_json(scene_deserializer, bytes)?)
+    }
+
+    fn serialize(scene: &DynamicScene, registry: &PropertyTypeRegistry) -> Result<Vec<u8>> {
+        Ok(scene.serialize_json(registry)?.into_bytes())
+    }
+}
+
+/// The YAML `.scn.yaml` format
+#[derive(Debug)]
+pub struct YamlSceneFormat;
+
+impl SceneFormat for YamlSceneFormat {
+    const EXTENSIONS: &'static [&'static str] = &["scn.yaml", "scn.yml"];
+
+    fn deserialize(bytes: &[u8], registry: &PropertyTypeRegistry) -> Result<DynamicScene> {
+        let scene_deserializer = SceneDeserializer {
+            property_type_registry: registry,
+        };
+        Ok(deserialize_yaml(scene_deserializer, bytes)?)
+    }
+
+    fn serialize(scene: &DynamicScene, registry: &PropertyTypeRegistry) -> Result<Vec<u8>> {
+        Ok(scene.serialize_yaml(registry)?.into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BinarySceneFormat, JsonSceneFormat, RonSceneFormat, SceneFormat, YamlSceneFormat};
+    use crate::{DynamicScene, Entity, SceneEntityId};
+    use bevy_property::{Properties, PropertiesVal, PropertyTypeRegistry};
+
+    fn round_trip<F: SceneFormat>() {
+        let registry = PropertyTypeRegistry::default();
+        let scene = DynamicScene {
+            entities: vec![Entity {
+                entity: 3,
+                components: vec![SceneEntityId::new("door").to_dynamic()],
+            }],
+            ..Default::default()
+        };
+        let bytes = F::serialize(&scene, &registry).unwrap();
+        let loaded = F::deserialize(&bytes, &registry).unwrap();
+        assert_eq!(loaded.entities.len(), 1);
+        assert_eq!(loaded.entities[0].entity, 3);
+        assert_eq!(
+            loaded.entities[0].components[0].prop_val::<String>("id"),
+            Some(&"door".to_string())
+        );
+    }
+
+    #[test]
+    fn scenes_round_trip_in_every_format() {
+        round_trip::<RonSceneFormat>();
+        round_trip::<BinarySceneFormat>();
+        round_trip::<JsonSceneFormat>();
+        round_trip::<YamlSceneFormat>();
+    }
+}
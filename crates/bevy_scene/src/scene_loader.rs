@@ -1,4 +1,7 @@
-use crate::serde::SceneDeserializer;
+use crate::{
+    serde::SaveGameDeserializer, BinarySceneFormat, DynamicScene, JsonSceneFormat, RonSceneFormat,
+    SaveGameRegistry, SceneFormat, YamlSceneFormat,
+};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::{FromResources, Resources};
@@ -7,23 +10,91 @@ use bevy_type_registry::TypeRegistry;
 use bevy_utils::BoxedFuture;
 use parking_lot::RwLock;
 use serde::de::DeserializeSeed;
-use std::sync::Arc;
+use std::{marker::PhantomData, sync::Arc};
 
+/// Loads [DynamicScene]s in the given [SceneFormat]
 #[derive(Debug)]
-pub struct SceneLoader {
+pub struct SceneFormatLoader<F> {
     property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+    marker: PhantomData<F>,
 }
 
-impl FromResources for SceneLoader {
+impl<F: SceneFormat> FromResources for SceneFormatLoader<F> {
     fn from_resources(resources: &Resources) -> Self {
         let type_registry = resources.get::<TypeRegistry>().unwrap();
-        SceneLoader {
+        SceneFormatLoader {
             property_type_registry: type_registry.property.clone(),
+            marker: PhantomData,
         }
     }
 }
 
-impl AssetLoader for SceneLoader {
+impl<F: SceneFormat> AssetLoader for SceneFormatLoader<F> {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let scene = F::deserialize(bytes, &self.property_type_registry.read())?;
+            set_scene_asset(load_context, scene);
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        F::EXTENSIONS
+    }
+}
+
+/// Loads RON `.scn` files written by [SceneSaver](crate::SceneSaver)
+pub type SceneLoader = SceneFormatLoader<RonSceneFormat>;
+
+/// Loads `.scnb` files written by [BinarySceneSaver](crate::BinarySceneSaver)
+pub type BinarySceneLoader = SceneFormatLoader<BinarySceneFormat>;
+
+/// Loads `.scn.json` files written by [JsonSceneSaver](crate::JsonSceneSaver)
+pub type JsonSceneLoader = SceneFormatLoader<JsonSceneFormat>;
+
+/// Loads `.scn.yaml` and `.scn.yml` files written by [YamlSceneSaver](crate::YamlSceneSaver)
+pub type YamlSceneLoader = SceneFormatLoader<YamlSceneFormat>;
+
+/// Sets the loaded scene as the default asset. Nested scenes without an asset source of their own are loaded from the
+/// source of the containing scene, and the containing scene holds strong handles to them.
+fn set_scene_asset(load_context: &mut LoadContext, mut scene: DynamicScene) {
+    let mut dependencies = Vec::new();
+    for instance in scene.instances.iter_mut() {
+        let mut nested_path = instance.scene.clone();
+        if nested_path.source().is_none() {
+            if let Some(source) = load_context.source() {
+                nested_path = nested_path.with_source(source.to_string());
+            }
+        }
+        instance.handle = load_context.get_handle(nested_path.get_id());
+        dependencies.push(nested_path);
+    }
+    load_context.set_default_asset(LoadedAsset::new(scene).with_dependencies(dependencies));
+}
+
+/// Loads `.sav` files written by [SaveGameSaver](crate::SaveGameSaver), migrating them to the current save game version
+#[derive(Debug)]
+pub struct SaveGameLoader {
+    property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+    save_game_registry: SaveGameRegistry,
+}
+
+impl FromResources for SaveGameLoader {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let save_game_registry = resources.get::<SaveGameRegistry>().unwrap();
+        SaveGameLoader {
+            property_type_registry: type_registry.property.clone(),
+            save_game_registry: (*save_game_registry).clone(),
+        }
+    }
+}
+
+impl AssetLoader for SaveGameLoader {
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
@@ -31,18 +102,84 @@ impl AssetLoader for SceneLoader {
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
             let registry = self.property_type_registry.read();
-            let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
-            let scene_deserializer = SceneDeserializer {
+            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+            let save_game_deserializer = SaveGameDeserializer {
                 property_type_registry: &registry,
             };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            let mut save_game = save_game_deserializer.deserialize(&mut deserializer)?;
+            self.save_game_registry
+                .schema
+                .read()
+                .migrate(&mut save_game)?;
+            load_context.set_default_asset(LoadedAsset::new(save_game));
             Ok(())
         })
     }
 
     fn extensions(&self) -> &[&str] {
-        static EXTENSIONS: &[&str] = &["scn"];
+        static EXTENSIONS: &[&str] = &["sav"];
         EXTENSIONS
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SceneLoader;
+    use crate::{test_app, DynamicScene};
+    use bevy_asset::{AddAsset, AssetPath, AssetServer, Assets, Handle, MemoryAssetIo};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn nested_scenes_load_from_the_containing_source() {
+        let mut app_builder = test_app();
+        app_builder
+            .init_asset_loader::<SceneLoader>()
+            .add_asset_source(
+            "mods",
+            MemoryAssetIo::new()
+                .with_asset(
+                    "levels/forest.scn",
+                    r#"(entities: [], instances: [(scene: "prefabs/tree.scn", overrides: [])])"#,
+                )
+                .with_asset("prefabs/tree.scn", "[]"),
+        );
+        let mut app = app_builder.app;
+        let level: Handle<DynamicScene> = app
+            .resources
+            .get::<AssetServer>()
+            .unwrap()
+            .load("mods://levels/forest.scn");
+        let tree = AssetPath::from("mods://prefabs/tree.scn").get_id();
+
+        let start = Instant::now();
+        loop {
+            app.update();
+            let scenes = app.resources.get::<Assets<DynamicScene>>().unwrap();
+            if scenes.get(&level).is_some() && scenes.get(tree).is_some() {
+                break;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "scenes did not load"
+            );
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        {
+            let scenes = app.resources.get::<Assets<DynamicScene>>().unwrap();
+            let instance = &scenes.get(&level).unwrap().instances[0];
+            assert_eq!(instance.scene, AssetPath::from("prefabs/tree.scn"));
+            assert_eq!(instance.handle.id, tree.into());
+            assert!(instance.handle.is_strong());
+        }
+
+        // the level's handle to the tree keeps it loaded
+        app.resources
+            .get::<AssetServer>()
+            .unwrap()
+            .free_unused_assets();
+        app.update();
+        let scenes = app.resources.get::<Assets<DynamicScene>>().unwrap();
+        assert!(scenes.get(tree).is_some());
+    }
+}
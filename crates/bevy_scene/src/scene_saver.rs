@@ -0,0 +1,98 @@
+use crate::{
+    serde::SaveGameSerializer, serialize_ron, BinarySceneFormat, DynamicScene, JsonSceneFormat,
+    RonSceneFormat, SaveGame, SceneFormat, YamlSceneFormat,
+};
+use anyhow::Result;
+use bevy_asset::{AssetSaver, DeferredSave};
+use bevy_ecs::{FromResources, Resources};
+use bevy_property::PropertyTypeRegistry;
+use bevy_type_registry::TypeRegistry;
+use parking_lot::RwLock;
+use std::{marker::PhantomData, path::Path, sync::Arc};
+
+/// Saves [DynamicScene]s in the given [SceneFormat]
+#[derive(Debug)]
+pub struct SceneFormatSaver<F> {
+    property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+    marker: PhantomData<F>,
+}
+
+impl<F: SceneFormat> FromResources for SceneFormatSaver<F> {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        SceneFormatSaver {
+            property_type_registry: type_registry.property.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<F: SceneFormat> AssetSaver for SceneFormatSaver<F> {
+    type Asset = DynamicScene;
+
+    fn save(&self, scene: &DynamicScene, _path: &Path) -> Result<Vec<u8>> {
+        F::serialize(scene, &self.property_type_registry.read())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        F::EXTENSIONS
+    }
+}
+
+/// Saves [DynamicScene]s as RON `.scn` files that [SceneLoader](crate::SceneLoader) can load
+pub type SceneSaver = SceneFormatSaver<RonSceneFormat>;
+
+/// Saves [DynamicScene]s as binary `.scnb` files that [BinarySceneLoader](crate::BinarySceneLoader) can load
+pub type BinarySceneSaver = SceneFormatSaver<BinarySceneFormat>;
+
+/// Saves [DynamicScene]s as JSON `.scn.json` files that [JsonSceneLoader](crate::JsonSceneLoader) can load
+pub type JsonSceneSaver = SceneFormatSaver<JsonSceneFormat>;
+
+/// Saves [DynamicScene]s as YAML `.scn.yaml` files that [YamlSceneLoader](crate::YamlSceneLoader) can load
+pub type YamlSceneSaver = SceneFormatSaver<YamlSceneFormat>;
+
+/// Saves [SaveGame]s as RON `.sav` files that [SaveGameLoader](crate::SaveGameLoader) can load
+#[derive(Debug)]
+pub struct SaveGameSaver {
+    property_type_registry: Arc<RwLock<PropertyTypeRegistry>>,
+}
+
+impl FromResources for SaveGameSaver {
+    fn from_resources(resources: &Resources) -> Self {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        SaveGameSaver {
+            property_type_registry: type_registry.property.clone(),
+        }
+    }
+}
+
+impl AssetSaver for SaveGameSaver {
+    type Asset = SaveGame;
+
+    fn save(&self, save_game: &SaveGame, _path: &Path) -> Result<Vec<u8>> {
+        let registry = self.property_type_registry.read();
+        let ron = serialize_ron(SaveGameSerializer {
+            save_game,
+            registry: &registry,
+        })?;
+        Ok(ron.into_bytes())
+    }
+
+    /// Save games can be large, so only a copy is made when saving. The copy is serialized on the task pool.
+    fn save_deferred(&self, save_game: &SaveGame, path: &Path) -> Result<DeferredSave> {
+        let save_game = save_game.clone();
+        let path = path.to_owned();
+        let property_type_registry = self.property_type_registry.clone();
+        Ok(Box::new(move || {
+            SaveGameSaver {
+                property_type_registry,
+            }
+            .save(&save_game, &path)
+        }))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        static EXTENSIONS: &[&str] = &["sav"];
+        EXTENSIONS
+    }
+}
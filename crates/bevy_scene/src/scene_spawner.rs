@@ -1,21 +1,75 @@
-use crate::{DynamicScene, Scene};
+use crate::{
+    dynamic_scene::write_resources,
+    scene_entity::{contains_refs, SceneEntities},
+    DynamicScene, DynamicSceneToWorldError, Entity, Scene, UnresolvedEntityReferences,
+};
 use bevy_app::prelude::*;
-use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_asset::{AssetEvent, AssetPath, Assets, Handle, HandleId};
 use bevy_ecs::{EntityMap, Resources, World};
-use bevy_type_registry::TypeRegistry;
-use bevy_utils::HashMap;
+use bevy_transform::components::Parent;
+use bevy_type_registry::{ComponentRegistry, TypeRegistry};
+use bevy_utils::{HashMap, HashSet};
+use std::any::TypeId;
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct InstanceInfo {
     entity_map: EntityMap,
+    /// The instances of the scenes nested in this one, in the order of [DynamicScene::instances]
+    nested_instances: Vec<InstanceInfo>,
+    /// The entity that the root entities of this instance are children of. See [SceneSpawner::spawn_as_child].
+    parent: Option<bevy_ecs::Entity>,
+    /// The components of this instance that contain [SceneEntityRef](crate::SceneEntityRef)s
+    entity_refs: Vec<(bevy_ecs::Entity, TypeId)>,
 }
 
+impl InstanceInfo {
+    /// Adds the entities of this instance and its nested instances to `entities`
+    fn entities(&self, entities: &mut Vec<bevy_ecs::Entity>) {
+        entities.extend(self.entity_map.values());
+        for nested_instance in self.nested_instances.iter() {
+            nested_instance.entities(entities);
+        }
+    }
+
+    /// Adds the components of this instance and its nested instances that contain entity references to `entity_refs`
+    fn entity_refs(&self, entity_refs: &mut Vec<(bevy_ecs::Entity, TypeId)>) {
+        entity_refs.extend(self.entity_refs.iter().cloned());
+        for nested_instance in self.nested_instances.iter() {
+            nested_instance.entity_refs(entity_refs);
+        }
+    }
+
+    /// Makes the entities without a [Parent] children of this instance's parent
+    fn set_parent(&self, world: &mut World) {
+        if let Some(parent) = self.parent {
+            let mut entities = Vec::new();
+            self.entities(&mut entities);
+            for entity in entities {
+                if world.get::<Parent>(entity).is_err() {
+                    world.insert_one(entity, Parent(parent)).unwrap();
+                }
+            }
+        }
+    }
+
+    fn despawn(&self, world: &mut World) {
+        for entity in self.entity_map.values() {
+            let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
+        }
+        for nested_instance in self.nested_instances.iter() {
+            nested_instance.despawn(world);
+        }
+    }
+}
+
+/// Identifies a spawned instance of a scene, for example to despawn it with [SceneSpawner::despawn_instance]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct InstanceId(Uuid);
+pub struct InstanceId(Uuid);
 
 impl InstanceId {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         InstanceId(Uuid::new_v4())
     }
@@ -26,10 +80,21 @@ pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
+    /// Maps scenes nested in spawned scenes to the spawned scenes that contain them, so that changes to prefabs
+    /// propagate to their instances
+    nested_scene_dependents: HashMap<Handle<DynamicScene>, HashSet<Handle<DynamicScene>>>,
     scene_asset_event_reader: EventReader<AssetEvent<DynamicScene>>,
-    dynamic_scenes_to_spawn: Vec<Handle<DynamicScene>>,
-    scenes_to_spawn: Vec<Handle<Scene>>,
+    dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
+    scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
+    instances_to_despawn: Vec<InstanceId>,
+    scenes_to_update: Vec<Handle<DynamicScene>>,
+    /// Instances whose root entities become children of the given entity once they are spawned
+    scenes_with_parent: Vec<(InstanceId, bevy_ecs::Entity)>,
+    /// Instances spawned since the last [SceneInstanceReady] events were sent
+    ready_instances: Vec<InstanceId>,
+    /// Spawned entities with a [SceneEntityId](crate::SceneEntityId)
+    scene_entities: SceneEntities,
 }
 
 #[derive(Error, Debug)]
@@ -40,76 +105,388 @@ pub enum SceneSpawnError {
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("Scene does not exist. Perhaps it is still loading?")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("Scene contains an instance of itself: {scene:?}")]
+    RecursiveInstance { scene: AssetPath<'static> },
+    #[error("Scene contains an unregistered resource.")]
+    UnregisteredResource { type_name: String },
+    #[error("Scene contains a resource that has not been inserted.")]
+    MissingResource { type_name: String },
+}
+
+impl From<DynamicSceneToWorldError> for SceneSpawnError {
+    fn from(error: DynamicSceneToWorldError) -> Self {
+        match error {
+            DynamicSceneToWorldError::UnregisteredComponent { type_name } => {
+                SceneSpawnError::UnregisteredComponent { type_name }
+            }
+            DynamicSceneToWorldError::UnregisteredResource { type_name } => {
+                SceneSpawnError::UnregisteredResource { type_name }
+            }
+            DynamicSceneToWorldError::MissingResource { type_name } => {
+                SceneSpawnError::MissingResource { type_name }
+            }
+        }
+    }
+}
+
+/// Sent once the entities of a spawned scene instance exist in the world
+#[derive(Debug, Clone, Copy)]
+pub struct SceneInstanceReady {
+    pub instance_id: InstanceId,
 }
 
 impl SceneSpawner {
-    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) {
-        self.dynamic_scenes_to_spawn.push(scene_handle);
+    pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.spawn_dynamic_with_id(scene_handle, instance_id, None);
+        instance_id
     }
 
-    pub fn spawn(&mut self, scene_handle: Handle<Scene>) {
-        self.scenes_to_spawn.push(scene_handle);
+    /// Spawns the scene with its root entities (the ones without a [Parent]) as children of `parent`
+    pub fn spawn_dynamic_as_child(
+        &mut self,
+        scene_handle: Handle<DynamicScene>,
+        parent: bevy_ecs::Entity,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.spawn_dynamic_with_id(scene_handle, instance_id, Some(parent));
+        instance_id
+    }
+
+    /// Queues an instance whose id was already handed out, such as by [SpawnSceneCommands](crate::SpawnSceneCommands)
+    pub(crate) fn spawn_dynamic_with_id(
+        &mut self,
+        scene_handle: Handle<DynamicScene>,
+        instance_id: InstanceId,
+        parent: Option<bevy_ecs::Entity>,
+    ) {
+        self.dynamic_scenes_to_spawn
+            .push((scene_handle, instance_id));
+        if let Some(parent) = parent {
+            self.scenes_with_parent.push((instance_id, parent));
+        }
+    }
+
+    pub fn spawn(&mut self, scene_handle: Handle<Scene>) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.spawn_with_id(scene_handle, instance_id, None);
+        instance_id
+    }
+
+    /// Spawns the scene with its root entities (the ones without a [Parent]) as children of `parent`
+    pub fn spawn_as_child(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        parent: bevy_ecs::Entity,
+    ) -> InstanceId {
+        let instance_id = InstanceId::new();
+        self.spawn_with_id(scene_handle, instance_id, Some(parent));
+        instance_id
+    }
+
+    /// Queues an instance whose id was already handed out, such as by [SpawnSceneCommands](crate::SpawnSceneCommands)
+    pub(crate) fn spawn_with_id(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        instance_id: InstanceId,
+        parent: Option<bevy_ecs::Entity>,
+    ) {
+        self.scenes_to_spawn.push((scene_handle, instance_id));
+        if let Some(parent) = parent {
+            self.scenes_with_parent.push((instance_id, parent));
+        }
     }
 
     pub fn despawn(&mut self, scene_handle: Handle<DynamicScene>) {
         self.scenes_to_despawn.push(scene_handle);
     }
 
+    pub fn despawn_instance(&mut self, instance_id: InstanceId) {
+        self.instances_to_despawn.push(instance_id);
+    }
+
+    /// Returns true once the entities of the instance exist in the world
+    pub fn instance_is_ready(&self, instance_id: InstanceId) -> bool {
+        self.spawned_instances.contains_key(&instance_id)
+    }
+
+    /// Returns the entities of a spawned instance, including those of the scenes nested in it
+    pub fn instance_entities(&self, instance_id: InstanceId) -> Option<Vec<bevy_ecs::Entity>> {
+        self.spawned_instances.get(&instance_id).map(|instance| {
+            let mut entities = Vec::new();
+            instance.entities(&mut entities);
+            entities
+        })
+    }
+
+    /// Returns the spawned entity with the given [SceneEntityId](crate::SceneEntityId)
+    pub fn get_scene_entity(&self, id: &str) -> Option<bevy_ecs::Entity> {
+        self.scene_entities.get(id)
+    }
+
+    /// Returns the components of spawned entities with [SceneEntityRef](crate::SceneEntityRef)s to ids that no spawned
+    /// entity has
+    pub fn unresolved_entity_references(&self) -> &[UnresolvedEntityReferences] {
+        self.scene_entities.unresolved()
+    }
+
     pub fn despawn_sync(
         &mut self,
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
-        if let Some(instance_ids) = self.spawned_dynamic_scenes.get(&scene_handle) {
+        if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
+            let mut entities = Vec::new();
             for instance_id in instance_ids {
-                if let Some(instance) = self.spawned_instances.get(&instance_id) {
-                    for entity in instance.entity_map.values() {
-                        let _ = world.despawn(entity); // Ignore the result, despawn only cares if it exists.
-                    }
+                if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+                    instance.entities(&mut entities);
+                    instance.despawn(world);
                 }
             }
-
-            self.spawned_dynamic_scenes.remove(&scene_handle);
+            self.scene_entities.remove(&entities);
+            self.remove_spawned_dynamic_scene(&scene_handle);
         }
         Ok(())
     }
 
+    /// Forgets a scene that no longer has spawned instances, so changes to the scenes nested in it are not tracked
+    fn remove_spawned_dynamic_scene(&mut self, scene_handle: &Handle<DynamicScene>) {
+        self.spawned_dynamic_scenes.remove(scene_handle);
+        self.nested_scene_dependents.retain(|_, dependents| {
+            dependents.remove(scene_handle);
+            !dependents.is_empty()
+        });
+    }
+
+    pub fn despawn_instance_sync(&mut self, world: &mut World, instance_id: InstanceId) {
+        if let Some(instance) = self.spawned_instances.remove(&instance_id) {
+            let mut entities = Vec::new();
+            instance.entities(&mut entities);
+            instance.despawn(world);
+            self.scene_entities.remove(&entities);
+        }
+        let mut despawned_dynamic_scenes = Vec::new();
+        for (scene_handle, instance_ids) in self.spawned_dynamic_scenes.iter_mut() {
+            instance_ids.retain(|id| *id != instance_id);
+            if instance_ids.is_empty() {
+                despawned_dynamic_scenes.push(scene_handle.clone_weak());
+            }
+        }
+        for scene_handle in despawned_dynamic_scenes {
+            self.remove_spawned_dynamic_scene(&scene_handle);
+        }
+        self.spawned_scenes.retain(|_, instance_ids| {
+            instance_ids.retain(|id| *id != instance_id);
+            !instance_ids.is_empty()
+        });
+    }
+
     pub fn spawn_dynamic_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
+        self.spawn_dynamic_instance(world, resources, scene_handle, instance_id)?;
+        self.resolve_entity_references(world, resources, &[instance_id]);
+        Ok(instance_id)
+    }
+
+    fn spawn_dynamic_instance(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: &Handle<DynamicScene>,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        let mut instance_info = InstanceInfo::default();
+        let nested_scenes = match Self::spawn_dynamic_internal(
+            world,
+            resources,
+            scene_handle,
+            &mut instance_info,
+        ) {
+            Ok(nested_scenes) => nested_scenes,
+            Err(err) => {
+                // entities written before the error was found are not part of any instance
+                instance_info.despawn(world);
+                return Err(err);
+            }
         };
-        Self::spawn_dynamic_internal(world, resources, scene_handle, &mut instance_info)?;
+        Self::write_scene_resources(resources, scene_handle)?;
         self.spawned_instances.insert(instance_id, instance_info);
         let spawned = self
             .spawned_dynamic_scenes
             .entry(scene_handle.clone())
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
+        self.add_nested_scene_dependents(scene_handle, nested_scenes);
+        self.ready_instances.push(instance_id);
         Ok(())
     }
 
+    /// Registers the entities of the given instances that have a [SceneEntityId](crate::SceneEntityId), then resolves
+    /// the [SceneEntityRef](crate::SceneEntityRef)s in their components. Ids are registered first, so instances
+    /// spawned together can refer to each other. References to ids that have not been spawned yet are resolved once
+    /// they are.
+    fn resolve_entity_references(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        instance_ids: &[InstanceId],
+    ) {
+        let mut entities = Vec::new();
+        let mut entity_refs = Vec::new();
+        for instance_id in instance_ids.iter() {
+            if let Some(instance_info) = self.spawned_instances.get(instance_id) {
+                instance_info.entities(&mut entities);
+                instance_info.entity_refs(&mut entity_refs);
+            }
+        }
+
+        self.scene_entities.add(world, &entities);
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let component_registry = type_registry.component.read();
+        self.scene_entities
+            .resolve(world, &component_registry, &entity_refs);
+    }
+
+    /// Spawns (or updates) an instance of the given scene and the scenes nested in it. Nothing is spawned unless the
+    /// scene and all of its nested scenes are loaded. Returns the handles of the nested scenes.
     fn spawn_dynamic_internal(
         world: &mut World,
         resources: &Resources,
         scene_handle: &Handle<DynamicScene>,
         instance_info: &mut InstanceInfo,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<Vec<Handle<DynamicScene>>, SceneSpawnError> {
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
         let scenes = resources.get::<Assets<DynamicScene>>().unwrap();
+        let mut nested_scenes = Vec::new();
+        Self::get_nested_scenes(&scenes, scene_handle, &mut Vec::new(), &mut nested_scenes)?;
+        let scene = scenes.get(scene_handle).unwrap();
+        Self::write_dynamic_scene(
+            world,
+            resources,
+            &component_registry,
+            &scenes,
+            scene,
+            instance_info,
+        )?;
+        Ok(nested_scenes)
+    }
+
+    /// Applies the resources of a scene. This only happens when a scene is spawned, so resources are not reset by
+    /// changes to the scene, and scenes nested in it do not apply their resources.
+    fn write_scene_resources(
+        resources: &Resources,
+        scene_handle: &Handle<DynamicScene>,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = resources.get::<TypeRegistry>().unwrap();
+        let scenes = resources.get::<Assets<DynamicScene>>().unwrap();
+        if let Some(scene) = scenes.get(scene_handle) {
+            write_resources(&scene.resources, resources, &type_registry.component.read())?;
+        }
+        Ok(())
+    }
+
+    fn get_nested_scenes(
+        scenes: &Assets<DynamicScene>,
+        scene_handle: &Handle<DynamicScene>,
+        stack: &mut Vec<HandleId>,
+        nested_scenes: &mut Vec<Handle<DynamicScene>>,
+    ) -> Result<(), SceneSpawnError> {
         let scene = scenes
             .get(scene_handle)
             .ok_or_else(|| SceneSpawnError::NonExistentScene {
                 handle: scene_handle.clone_weak(),
             })?;
+        stack.push(scene_handle.id);
+        for instance in scene.instances.iter() {
+            let nested_handle = instance.handle.clone_weak();
+            if stack.contains(&nested_handle.id) {
+                return Err(SceneSpawnError::RecursiveInstance {
+                    scene: instance.scene.clone(),
+                });
+            }
+            Self::get_nested_scenes(scenes, &nested_handle, stack, nested_scenes)?;
+            nested_scenes.push(nested_handle);
+        }
+        stack.pop();
+        Ok(())
+    }
+
+    fn write_dynamic_scene(
+        world: &mut World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+        scenes: &Assets<DynamicScene>,
+        scene: &DynamicScene,
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        // writing the scene resets the references in its components
+        instance_info.entity_refs.clear();
+        Self::write_entities(
+            world,
+            resources,
+            component_registry,
+            &scene.entities,
+            instance_info,
+        )?;
+
+        // instances removed from the scene since it was last spawned
+        for nested_instance in instance_info.nested_instances.drain(
+            scene
+                .instances
+                .len()
+                .min(instance_info.nested_instances.len())..,
+        ) {
+            nested_instance.despawn(world);
+        }
+        instance_info
+            .nested_instances
+            .resize_with(scene.instances.len(), InstanceInfo::default);
+
+        for (instance, nested_instance) in scene
+            .instances
+            .iter()
+            .zip(instance_info.nested_instances.iter_mut())
+        {
+            let nested_scene =
+                scenes
+                    .get(&instance.handle)
+                    .ok_or_else(|| SceneSpawnError::NonExistentScene {
+                        handle: instance.handle.clone_weak(),
+                    })?;
+            Self::write_dynamic_scene(
+                world,
+                resources,
+                component_registry,
+                scenes,
+                nested_scene,
+                nested_instance,
+            )?;
+            // overrides are written after the nested scene, so they win over its values
+            Self::write_entities(
+                world,
+                resources,
+                component_registry,
+                &instance.overrides,
+                nested_instance,
+            )?;
+        }
+        Ok(())
+    }
 
-        for scene_entity in scene.entities.iter() {
+    fn write_entities(
+        world: &mut World,
+        resources: &Resources,
+        component_registry: &ComponentRegistry,
+        scene_entities: &[Entity],
+        instance_info: &mut InstanceInfo,
+    ) -> Result<(), SceneSpawnError> {
+        for scene_entity in scene_entities.iter() {
             let entity = *instance_info
                 .entity_map
                 // TODO: use Entity type directly in scenes to properly encode generation / avoid the need to patch things up?
@@ -129,21 +506,49 @@ impl SceneSpawner {
                     component_registration
                         .add_property_to_entity(world, resources, entity, component);
                 }
+                if contains_refs(component) {
+                    instance_info
+                        .entity_refs
+                        .push((entity, component_registration.ty));
+                }
             }
         }
         Ok(())
     }
 
+    fn add_nested_scene_dependents(
+        &mut self,
+        scene_handle: &Handle<DynamicScene>,
+        nested_scenes: Vec<Handle<DynamicScene>>,
+    ) {
+        for nested_scene in nested_scenes {
+            self.nested_scene_dependents
+                .entry(nested_scene)
+                .or_default()
+                .insert(scene_handle.clone_weak());
+        }
+    }
+
     pub fn spawn_sync(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handle: Handle<Scene>,
-    ) -> Result<(), SceneSpawnError> {
+    ) -> Result<InstanceId, SceneSpawnError> {
         let instance_id = InstanceId::new();
-        let mut instance_info = InstanceInfo {
-            entity_map: EntityMap::default(),
-        };
+        self.spawn_instance(world, resources, scene_handle, instance_id)?;
+        self.resolve_entity_references(world, resources, &[instance_id]);
+        Ok(instance_id)
+    }
+
+    fn spawn_instance(
+        &mut self,
+        world: &mut World,
+        resources: &Resources,
+        scene_handle: Handle<Scene>,
+        instance_id: InstanceId,
+    ) -> Result<(), SceneSpawnError> {
+        let mut instance_info = InstanceInfo::default();
         let type_registry = resources.get::<TypeRegistry>().unwrap();
         let component_registry = type_registry.component.read();
         let scenes = resources.get::<Assets<Scene>>().unwrap();
@@ -155,7 +560,7 @@ impl SceneSpawner {
                 })?;
 
         for archetype in scene.world.archetypes() {
-            for scene_entity in archetype.iter_entities() {
+            for (index, scene_entity) in archetype.iter_entities().enumerate() {
                 let entity = *instance_info
                     .entity_map
                     .entry(*scene_entity)
@@ -169,6 +574,13 @@ impl SceneSpawner {
                             *scene_entity,
                             entity,
                         );
+                        let properties =
+                            component_registration.get_component_properties(archetype, index);
+                        if properties.iter_props().any(contains_refs) {
+                            instance_info
+                                .entity_refs
+                                .push((entity, component_registration.ty));
+                        }
                     }
                 }
             }
@@ -184,29 +596,64 @@ impl SceneSpawner {
             .entry(scene_handle)
             .or_insert_with(Vec::new);
         spawned.push(instance_id);
+        self.ready_instances.push(instance_id);
         Ok(())
     }
 
+    /// Updates the spawned instances of the given scenes, and of the spawned scenes that have them nested as instances.
+    /// Instances whose nested scenes are still loading are updated once they have loaded. Scenes that can't be updated,
+    /// for example because they now contain an instance of themselves, are logged and keep their current entities.
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
         resources: &Resources,
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
+        let mut handles_to_update = HashSet::default();
         for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
+            if self.spawned_dynamic_scenes.contains_key(scene_handle) {
+                handles_to_update.insert(scene_handle.clone_weak());
+            }
+            if let Some(dependents) = self.nested_scene_dependents.get(scene_handle) {
+                handles_to_update.extend(dependents.iter().cloned());
+            }
+        }
+
+        let mut updated_instances = Vec::new();
+        for scene_handle in handles_to_update {
+            let mut nested_scenes = Vec::new();
+            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(&scene_handle) {
                 for instance_id in spawned_instances.iter() {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
-                        Self::spawn_dynamic_internal(
+                        match Self::spawn_dynamic_internal(
                             world,
                             resources,
-                            scene_handle,
+                            &scene_handle,
                             instance_info,
-                        )?;
+                        ) {
+                            Ok(instance_nested_scenes) => {
+                                // entities added to the scene need to be parented as well
+                                instance_info.set_parent(world);
+                                nested_scenes = instance_nested_scenes;
+                                updated_instances.push(*instance_id);
+                            }
+                            Err(SceneSpawnError::NonExistentScene { .. }) => {
+                                self.scenes_to_update.push(scene_handle.clone_weak());
+                                break;
+                            }
+                            Err(err) => {
+                                log::error!("Failed to update scene {:?}: {}", scene_handle, err);
+                                break;
+                            }
+                        }
                     }
                 }
             }
+            self.add_nested_scene_dependents(&scene_handle, nested_scenes);
         }
+
+        // updating an instance resets the references in its components
+        self.resolve_entity_references(world, resources, &updated_instances);
         Ok(())
     }
 
@@ -216,61 +663,519 @@ impl SceneSpawner {
         for scene_handle in scenes_to_despawn {
             self.despawn_sync(world, scene_handle)?;
         }
+
+        for instance_id in std::mem::take(&mut self.instances_to_despawn) {
+            self.despawn_instance_sync(world, instance_id);
+        }
         Ok(())
     }
 
+    /// Spawns the scenes queued with [SceneSpawner::spawn] and [SceneSpawner::spawn_dynamic]. Scenes that are still
+    /// loading stay queued. Scenes that can't be spawned, for example because they contain an unregistered component,
+    /// are logged and dropped from the queue.
     pub fn spawn_queued_scenes(
         &mut self,
         world: &mut World,
         resources: &Resources,
     ) -> Result<(), SceneSpawnError> {
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
+        let mut spawned_instances = Vec::new();
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_dynamic_sync(world, resources, &scene_handle) {
-                Ok(_) => {}
-                Err(SceneSpawnError::NonExistentScene { .. }) => {
-                    self.dynamic_scenes_to_spawn.push(scene_handle)
+        for (scene_handle, instance_id) in scenes_to_spawn {
+            match self.spawn_dynamic_instance(world, resources, &scene_handle, instance_id) {
+                Ok(_) => spawned_instances.push(instance_id),
+                Err(SceneSpawnError::NonExistentScene { .. }) => self
+                    .dynamic_scenes_to_spawn
+                    .push((scene_handle, instance_id)),
+                Err(err) => {
+                    log::error!("Failed to spawn scene {:?}: {}", scene_handle, err);
+                    self.scenes_with_parent.retain(|(id, _)| *id != instance_id);
                 }
-                Err(err) => return Err(err),
             }
         }
 
         let scenes_to_spawn = std::mem::take(&mut self.scenes_to_spawn);
 
-        for scene_handle in scenes_to_spawn {
-            match self.spawn_sync(world, resources, scene_handle) {
-                Ok(_) => {}
+        for (scene_handle, instance_id) in scenes_to_spawn {
+            match self.spawn_instance(world, resources, scene_handle, instance_id) {
+                Ok(_) => spawned_instances.push(instance_id),
                 Err(SceneSpawnError::NonExistentRealScene { handle }) => {
-                    self.scenes_to_spawn.push(handle)
+                    self.scenes_to_spawn.push((handle, instance_id))
+                }
+                Err(err) => {
+                    log::error!("Failed to spawn scene instance {:?}: {}", instance_id, err);
+                    self.scenes_with_parent.retain(|(id, _)| *id != instance_id);
                 }
-                Err(err) => return Err(err),
             }
         }
 
+        self.set_scene_instance_parents(world);
+        self.resolve_entity_references(world, resources, &spawned_instances);
         Ok(())
     }
+
+    /// Parents the root entities of instances spawned with [SceneSpawner::spawn_as_child]. Instances that have not
+    /// been spawned yet are parented once they are.
+    fn set_scene_instance_parents(&mut self, world: &mut World) {
+        for (instance_id, parent) in std::mem::take(&mut self.scenes_with_parent) {
+            if let Some(instance_info) = self.spawned_instances.get_mut(&instance_id) {
+                instance_info.parent = Some(parent);
+                instance_info.set_parent(world);
+            } else {
+                self.scenes_with_parent.push((instance_id, parent));
+            }
+        }
+    }
 }
 
 pub fn scene_spawner_system(world: &mut World, resources: &mut Resources) {
     let mut scene_spawner = resources.get_mut::<SceneSpawner>().unwrap();
     let scene_asset_events = resources.get::<Events<AssetEvent<DynamicScene>>>().unwrap();
 
-    let mut updated_spawned_scenes = Vec::new();
+    let mut updated_spawned_scenes = std::mem::take(&mut scene_spawner.scenes_to_update);
     for event in scene_spawner
         .scene_asset_event_reader
         .iter(&scene_asset_events)
     {
         if let AssetEvent::Modified { handle } = event {
-            if scene_spawner.spawned_dynamic_scenes.contains_key(handle) {
+            if scene_spawner.spawned_dynamic_scenes.contains_key(handle)
+                || scene_spawner.nested_scene_dependents.contains_key(handle)
+            {
                 updated_spawned_scenes.push(handle.clone_weak());
             }
         }
     }
 
-    scene_spawner.despawn_queued_scenes(world).unwrap();
-    scene_spawner.spawn_queued_scenes(world, resources).unwrap();
-    scene_spawner
-        .update_spawned_scenes(world, resources, &updated_spawned_scenes)
-        .unwrap();
+    // scenes that fail to spawn or update are logged, so broken scene files don't stop the app
+    let result = scene_spawner
+        .despawn_queued_scenes(world)
+        .and_then(|_| scene_spawner.spawn_queued_scenes(world, resources))
+        .and_then(|_| {
+            scene_spawner.update_spawned_scenes(world, resources, &updated_spawned_scenes)
+        });
+    if let Err(err) = result {
+        log::error!("Failed to update scenes: {}", err);
+    }
+
+    let mut instance_ready_events = resources.get_mut::<Events<SceneInstanceReady>>().unwrap();
+    for instance_id in scene_spawner.ready_instances.drain(..) {
+        instance_ready_events.send(SceneInstanceReady { instance_id });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scene_spawner_system, SceneInstanceReady, SceneSpawner};
+    use crate::{
+        test_app, DynamicScene, Entity, Scene, SceneEntityId, SceneEntityRef, SceneInstance,
+        SpawnSceneCommands,
+    };
+    use bevy_app::prelude::*;
+    use bevy_asset::{AddAsset, AssetPath, Assets, Handle};
+    use bevy_ecs::{Commands, World};
+    use bevy_property::{DynamicProperties, Properties};
+    use bevy_transform::components::Parent;
+    use bevy_type_registry::TypeRegistry;
+
+    #[derive(Properties, Default)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    fn health(current: Option<f32>, max: Option<f32>) -> DynamicProperties {
+        let mut properties = DynamicProperties::map();
+        properties.type_name = std::any::type_name::<Health>().to_string();
+        if let Some(current) = current {
+            properties.set("current", current);
+        }
+        if let Some(max) = max {
+            properties.set("max", max);
+        }
+        properties
+    }
+
+    fn prefab(max: f32) -> DynamicScene {
+        DynamicScene {
+            entities: vec![Entity {
+                entity: 0,
+                components: vec![health(Some(max), Some(max))],
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn prefab_instances_apply_overrides_and_follow_prefab_changes() {
+        let App {
+            mut world,
+            resources,
+            ..
+        } = test_app().app;
+        resources
+            .get::<TypeRegistry>()
+            .unwrap()
+            .component
+            .write()
+            .register::<Health>();
+        let mut scenes = resources.get_mut::<Assets<DynamicScene>>().unwrap();
+
+        let prefab_path = AssetPath::from("prefabs/enemy.scn");
+        let prefab_handle: Handle<DynamicScene> = scenes.set(prefab_path.clone(), prefab(10.0));
+        let level_handle = scenes.add(DynamicScene {
+            instances: vec![
+                SceneInstance::new(prefab_path.clone()),
+                SceneInstance::new(prefab_path.clone()).with_override(0, health(Some(5.0), None)),
+            ],
+            ..Default::default()
+        });
+        drop(scenes);
+
+        let mut scene_spawner = SceneSpawner::default();
+        let instance_id = scene_spawner
+            .spawn_dynamic_sync(&mut world, &resources, &level_handle)
+            .unwrap();
+        let mut values = world
+            .query::<&Health>()
+            .iter()
+            .map(|health| (health.current, health.max))
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(values, vec![(5.0, 10.0), (10.0, 10.0)]);
+
+        resources
+            .get_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .set(prefab_handle.clone_weak(), prefab(20.0));
+        scene_spawner
+            .update_spawned_scenes(&mut world, &resources, &[prefab_handle])
+            .unwrap();
+        let mut values = world
+            .query::<&Health>()
+            .iter()
+            .map(|health| (health.current, health.max))
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(values, vec![(5.0, 20.0), (20.0, 20.0)]);
+
+        // the prefab is no longer tracked once the last instance containing it is despawned
+        scene_spawner.despawn_instance_sync(&mut world, instance_id);
+        assert_eq!(world.query::<&Health>().iter().count(), 0);
+        assert!(scene_spawner.spawned_dynamic_scenes.is_empty());
+        assert!(scene_spawner.nested_scene_dependents.is_empty());
+    }
+
+    #[derive(Properties, Default)]
+    struct Score {
+        points: u32,
+    }
+
+    fn score(points: u32) -> DynamicProperties {
+        let mut properties = DynamicProperties::map();
+        properties.type_name = std::any::type_name::<Score>().to_string();
+        properties.set("points", points);
+        properties
+    }
+
+    #[test]
+    fn resources_are_only_applied_when_spawning_the_top_level_scene() {
+        let mut app_builder = test_app();
+        app_builder.init_resource::<Score>();
+        let App {
+            mut world,
+            resources,
+            ..
+        } = app_builder.app;
+        resources
+            .get::<TypeRegistry>()
+            .unwrap()
+            .component
+            .write()
+            .resources
+            .register::<Score>();
+        let mut scenes = resources.get_mut::<Assets<DynamicScene>>().unwrap();
+        let prefab_path = AssetPath::from("prefabs/coin.scn");
+        scenes.set(
+            prefab_path.clone(),
+            DynamicScene {
+                resources: vec![score(5)],
+                ..Default::default()
+            },
+        );
+        let level_handle = scenes.add(DynamicScene {
+            instances: vec![SceneInstance::new(prefab_path)],
+            resources: vec![score(1)],
+            ..Default::default()
+        });
+        drop(scenes);
+
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner
+            .spawn_dynamic_sync(&mut world, &resources, &level_handle)
+            .unwrap();
+        assert_eq!(resources.get::<Score>().unwrap().points, 1);
+
+        resources.get_mut::<Score>().unwrap().points = 3;
+        scene_spawner
+            .update_spawned_scenes(&mut world, &resources, &[level_handle])
+            .unwrap();
+        assert_eq!(resources.get::<Score>().unwrap().points, 3);
+    }
+
+    #[derive(Properties, Default)]
+    struct Door {
+        switch: SceneEntityRef,
+    }
+
+    fn scene_with(components: Vec<DynamicProperties>) -> DynamicScene {
+        DynamicScene {
+            entities: vec![Entity {
+                entity: 0,
+                components,
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn door(switch: &str) -> DynamicProperties {
+        let mut properties = DynamicProperties::map();
+        properties.type_name = std::any::type_name::<Door>().to_string();
+        properties.set("switch", SceneEntityRef::new(switch));
+        properties
+    }
+
+    #[test]
+    fn entity_references_resolve_across_scenes() {
+        let App {
+            mut world,
+            resources,
+            ..
+        } = test_app().app;
+        {
+            let type_registry = resources.get::<TypeRegistry>().unwrap();
+            let mut component_registry = type_registry.component.write();
+            component_registry.register::<Door>();
+            component_registry.register::<SceneEntityId>();
+        }
+        let mut scenes = resources.get_mut::<Assets<DynamicScene>>().unwrap();
+        let door_handle = scenes.add(scene_with(vec![door("level_2/switch")]));
+        let switch_handle = scenes.add(scene_with(vec![
+            SceneEntityId::new("level_2/switch").to_dynamic()
+        ]));
+        let late_door_handle = scenes.add(scene_with(vec![door("late_switch")]));
+        let late_switch_handle = scenes.add(scene_with(vec![
+            SceneEntityId::new("late_switch").to_dynamic()
+        ]));
+        drop(scenes);
+
+        // the door is spawned first, but scenes spawned together can refer to each other
+        let mut scene_spawner = SceneSpawner::default();
+        scene_spawner.spawn_dynamic(door_handle);
+        scene_spawner.spawn_dynamic(switch_handle.clone());
+        scene_spawner
+            .spawn_queued_scenes(&mut world, &resources)
+            .unwrap();
+        let switch = scene_spawner.get_scene_entity("level_2/switch").unwrap();
+        let doors = world
+            .query::<&Door>()
+            .iter()
+            .map(|door| door.switch.entity())
+            .collect::<Vec<_>>();
+        assert_eq!(doors, vec![Some(switch)]);
+
+        // references to entities that have not been spawned yet are resolved once they are
+        let late_door = scene_spawner
+            .spawn_dynamic_sync(&mut world, &resources, &late_door_handle)
+            .unwrap();
+        let late_door = scene_spawner.instance_entities(late_door).unwrap()[0];
+        assert_eq!(world.get::<Door>(late_door).unwrap().switch.entity(), None);
+        let unresolved = scene_spawner.unresolved_entity_references();
+        assert_eq!(unresolved.len(), 1);
+        assert_eq!(unresolved[0].entity, late_door);
+        assert_eq!(unresolved[0].component, "Door");
+        assert_eq!(unresolved[0].ids, vec!["late_switch".to_string()]);
+        scene_spawner
+            .spawn_dynamic_sync(&mut world, &resources, &late_switch_handle)
+            .unwrap();
+        let late_switch = scene_spawner.get_scene_entity("late_switch").unwrap();
+        assert_eq!(
+            world.get::<Door>(late_door).unwrap().switch.entity(),
+            Some(late_switch)
+        );
+        assert!(scene_spawner.unresolved_entity_references().is_empty());
+
+        scene_spawner
+            .despawn_sync(&mut world, switch_handle)
+            .unwrap();
+        assert!(scene_spawner.get_scene_entity("level_2/switch").is_none());
+    }
+
+    #[test]
+    fn spawn_scene_instances_as_children() {
+        let mut app_builder = test_app();
+        app_builder
+            .add_asset::<Scene>()
+            .add_event::<SceneInstanceReady>()
+            .init_resource::<SceneSpawner>();
+        let App {
+            mut world,
+            mut resources,
+            ..
+        } = app_builder.app;
+        resources
+            .get::<TypeRegistry>()
+            .unwrap()
+            .component
+            .write()
+            .register::<Health>();
+        let mut scene_world = World::default();
+        scene_world.spawn((Health {
+            current: 1.0,
+            max: 1.0,
+        },));
+        scene_world.spawn((Health {
+            current: 2.0,
+            max: 2.0,
+        },));
+        let scene_handle = resources
+            .get_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(Scene::new(scene_world));
+
+        let parent = world.spawn(());
+        let instance_id = resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .spawn_as_child(scene_handle, parent);
+        scene_spawner_system(&mut world, &mut resources);
+
+        let scene_spawner = resources.get::<SceneSpawner>().unwrap();
+        assert!(scene_spawner.instance_is_ready(instance_id));
+        let entities = scene_spawner.instance_entities(instance_id).unwrap();
+        assert_eq!(entities.len(), 2);
+        for entity in entities.iter() {
+            assert_eq!(world.get::<Parent>(*entity).unwrap().0, parent);
+        }
+        drop(scene_spawner);
+
+        let events = resources.get::<Events<SceneInstanceReady>>().unwrap();
+        let ready = EventReader::<SceneInstanceReady>::default()
+            .iter(&events)
+            .map(|event| event.instance_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ready, vec![instance_id]);
+        drop(events);
+
+        resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .despawn_instance(instance_id);
+        scene_spawner_system(&mut world, &mut resources);
+        assert!(!resources
+            .get::<SceneSpawner>()
+            .unwrap()
+            .instance_is_ready(instance_id));
+        assert_eq!(world.query::<&Health>().iter().count(), 0);
+    }
+
+    #[test]
+    fn broken_scenes_do_not_stop_other_scenes_from_spawning() {
+        let App {
+            mut world,
+            resources,
+            ..
+        } = test_app().app;
+        resources
+            .get::<TypeRegistry>()
+            .unwrap()
+            .component
+            .write()
+            .register::<Health>();
+        let mut scenes = resources.get_mut::<Assets<DynamicScene>>().unwrap();
+        let recursive_path = AssetPath::from("prefabs/recursive.scn");
+        let recursive_handle: Handle<DynamicScene> = scenes.set(
+            recursive_path.clone(),
+            DynamicScene {
+                entities: prefab(1.0).entities,
+                instances: vec![SceneInstance::new(recursive_path.clone())],
+                ..Default::default()
+            },
+        );
+        let unregistered_handle = scenes.add(scene_with(vec![door("switch")]));
+        let prefab_handle = scenes.add(prefab(10.0));
+        drop(scenes);
+
+        let mut scene_spawner = SceneSpawner::default();
+        let recursive = scene_spawner.spawn_dynamic(recursive_handle);
+        let unregistered = scene_spawner.spawn_dynamic(unregistered_handle);
+        let valid = scene_spawner.spawn_dynamic(prefab_handle.clone());
+        scene_spawner
+            .spawn_queued_scenes(&mut world, &resources)
+            .unwrap();
+        assert!(!scene_spawner.instance_is_ready(recursive));
+        assert!(!scene_spawner.instance_is_ready(unregistered));
+        assert!(scene_spawner.instance_is_ready(valid));
+        assert!(scene_spawner.dynamic_scenes_to_spawn.is_empty());
+        assert_eq!(world.query::<&Health>().iter().count(), 1);
+
+        // a prefab that becomes recursive while it is spawned keeps its entities
+        resources.get_mut::<Assets<DynamicScene>>().unwrap().set(
+            prefab_handle.clone_weak(),
+            DynamicScene {
+                instances: vec![SceneInstance::new(recursive_path)],
+                ..prefab(20.0)
+            },
+        );
+        scene_spawner
+            .update_spawned_scenes(&mut world, &resources, &[prefab_handle])
+            .unwrap();
+        assert!(scene_spawner.instance_is_ready(valid));
+        assert_eq!(world.query::<&Health>().iter().count(), 1);
+    }
+
+    #[test]
+    fn despawning_a_scene_despawns_instances_spawned_with_commands() {
+        let mut app_builder = test_app();
+        app_builder
+            .add_event::<SceneInstanceReady>()
+            .init_resource::<SceneSpawner>();
+        let App {
+            mut world,
+            mut resources,
+            ..
+        } = app_builder.app;
+        resources
+            .get::<TypeRegistry>()
+            .unwrap()
+            .component
+            .write()
+            .register::<Health>();
+        let scene_handle = resources
+            .get_mut::<Assets<DynamicScene>>()
+            .unwrap()
+            .add(prefab(10.0));
+
+        let parent = world.spawn(());
+        let mut commands = Commands::default();
+        let instance_id = commands.spawn_dynamic_scene_as_child(scene_handle.clone(), parent);
+        commands.apply(&mut world, &mut resources);
+        scene_spawner_system(&mut world, &mut resources);
+
+        let scene_spawner = resources.get::<SceneSpawner>().unwrap();
+        assert!(scene_spawner.instance_is_ready(instance_id));
+        let entities = scene_spawner.instance_entities(instance_id).unwrap();
+        assert_eq!(world.get::<Parent>(entities[0]).unwrap().0, parent);
+        drop(scene_spawner);
+
+        resources
+            .get_mut::<SceneSpawner>()
+            .unwrap()
+            .despawn(scene_handle);
+        scene_spawner_system(&mut world, &mut resources);
+        let scene_spawner = resources.get::<SceneSpawner>().unwrap();
+        assert!(!scene_spawner.instance_is_ready(instance_id));
+        assert!(scene_spawner.instance_entities(instance_id).is_none());
+        assert!(scene_spawner.spawned_instances.is_empty());
+        assert_eq!(world.query::<&Health>().iter().count(), 0);
+    }
 }
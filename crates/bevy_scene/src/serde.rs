@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Entity};
+use crate::{DynamicScene, Entity, SaveGame, SceneInstance};
 use anyhow::Result;
 use bevy_property::{
     property_serde::{DynamicPropertiesDeserializer, DynamicPropertiesSerializer},
@@ -21,13 +21,71 @@ impl<'a> SceneSerializer<'a> {
     }
 }
 
+/// Scenes without resources or nested instances are written as a plain list of entities. Other scenes are written as
+/// a struct:
+///
+/// ```ron
+/// (
+///   entities: [ /* entities */ ],
+///   resources: [ /* resources, in the same format as components */ ],
+///   instances: [
+///     (
+///       scene: "prefabs/tree.scn",
+///       overrides: [ /* entities, with only the overridden properties */ ],
+///     ),
+///   ],
+/// )
+/// ```
+///
+/// Formats that are not self-describing, such as the binary `.scnb` format, always use the struct.
 impl<'a> Serialize for SceneSerializer<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let entities = EntitiesSerializer {
+            entities: &self.scene.entities,
+            registry: self.registry,
+        };
+        if serializer.is_human_readable()
+            && self.scene.instances.is_empty()
+            && self.scene.resources.is_empty()
+        {
+            return entities.serialize(serializer);
+        }
+
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 3)?;
+        state.serialize_field(SCENE_FIELD_ENTITIES, &entities)?;
+        state.serialize_field(
+            SCENE_FIELD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.scene.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SCENE_FIELD_INSTANCES,
+            &SceneInstancesSerializer {
+                instances: &self.scene.instances,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -37,6 +95,50 @@ impl<'a> Serialize for SceneSerializer<'a> {
     }
 }
 
+pub struct SceneInstancesSerializer<'a> {
+    pub instances: &'a [SceneInstance],
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for SceneInstancesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.instances.len()))?;
+        for instance in self.instances.iter() {
+            state.serialize_element(&SceneInstanceSerializer {
+                instance,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+pub struct SceneInstanceSerializer<'a> {
+    pub instance: &'a SceneInstance,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for SceneInstanceSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(INSTANCE_STRUCT, 2)?;
+        state.serialize_field(INSTANCE_FIELD_SCENE, &self.instance.scene.to_string())?;
+        state.serialize_field(
+            INSTANCE_FIELD_OVERRIDES,
+            &EntitiesSerializer {
+                entities: &self.instance.overrides,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
 pub struct EntitySerializer<'a> {
     pub entity: &'a Entity,
     pub registry: &'a PropertyTypeRegistry,
@@ -92,12 +194,165 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     where
         D: serde::Deserializer<'de>,
     {
-        let mut scene = DynamicScene::default();
-        scene.entities = deserializer.deserialize_seq(SceneEntitySeqVisiter {
+        let visiter = SceneVisiter {
             property_type_registry: self.property_type_registry,
-        })?;
+        };
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visiter)
+        } else {
+            deserializer.deserialize_struct(
+                SCENE_STRUCT,
+                &[
+                    SCENE_FIELD_ENTITIES,
+                    SCENE_FIELD_RESOURCES,
+                    SCENE_FIELD_INSTANCES,
+                ],
+                SceneStructVisiter(visiter),
+            )
+        }
+    }
+}
 
-        Ok(scene)
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Entities,
+    Resources,
+    Instances,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+pub const SCENE_FIELD_RESOURCES: &str = "resources";
+pub const SCENE_FIELD_INSTANCES: &str = "instances";
+
+struct SceneVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisiter<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of entities or scene struct")
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entities = SceneEntitySeqVisiter {
+            property_type_registry: self.property_type_registry,
+        }
+        .visit_seq(seq)?;
+        Ok(DynamicScene {
+            entities,
+            ..Default::default()
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entities = None;
+        let mut resources = None;
+        let mut instances = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntityVecDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+                SceneField::Resources => {
+                    if resources.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.property_type_registry,
+                    })?);
+                }
+                SceneField::Instances => {
+                    if instances.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_INSTANCES));
+                    }
+                    instances = Some(map.next_value_seed(SceneInstanceVecDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+            }
+        }
+
+        Ok(DynamicScene {
+            entities: entities.unwrap_or_default(),
+            instances: instances.unwrap_or_default(),
+            resources: resources.unwrap_or_default(),
+        })
+    }
+}
+
+/// Reads the struct form of a scene from formats that are not self-describing. These present structs as sequences, so
+/// [SceneVisiter::visit_seq] can't be used.
+struct SceneStructVisiter<'a>(SceneVisiter<'a>);
+
+impl<'a, 'de> Visitor<'de> for SceneStructVisiter<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let registry = self.0.property_type_registry;
+        let entities = seq
+            .next_element_seed(SceneEntityVecDeserializer {
+                property_type_registry: registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+        let resources = seq
+            .next_element_seed(ComponentVecDeserializer { registry })?
+            .ok_or_else(|| Error::missing_field(SCENE_FIELD_RESOURCES))?;
+        let instances = seq
+            .next_element_seed(SceneInstanceVecDeserializer {
+                property_type_registry: registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SCENE_FIELD_INSTANCES))?;
+        Ok(DynamicScene {
+            entities,
+            resources,
+            instances,
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.0.visit_map(map)
+    }
+}
+
+pub struct SceneEntityVecDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntityVecDeserializer<'a> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisiter {
+            property_type_registry: self.property_type_registry,
+        })
     }
 }
 
@@ -171,6 +426,21 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisiter<'a> {
         formatter.write_str("entities")
     }
 
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let entity = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_ENTITY))?;
+        let components = seq
+            .next_element_seed(ComponentVecDeserializer {
+                registry: self.registry,
+            })?
+            .ok_or_else(|| Error::missing_field(ENTITY_FIELD_COMPONENTS))?;
+        Ok(Entity { entity, components })
+    }
+
     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
     where
         A: MapAccess<'de>,
@@ -211,6 +481,143 @@ impl<'a, 'de> Visitor<'de> for SceneEntityVisiter<'a> {
     }
 }
 
+pub struct SceneInstanceVecDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneInstanceVecDeserializer<'a> {
+    type Value = Vec<SceneInstance>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneInstanceSeqVisiter {
+            property_type_registry: self.property_type_registry,
+        })
+    }
+}
+
+struct SceneInstanceSeqVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneInstanceSeqVisiter<'a> {
+    type Value = Vec<SceneInstance>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of scene instances")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut instances = Vec::new();
+        while let Some(instance) = seq.next_element_seed(SceneInstanceDeserializer {
+            property_type_registry: self.property_type_registry,
+        })? {
+            instances.push(instance);
+        }
+
+        Ok(instances)
+    }
+}
+
+pub struct SceneInstanceDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneInstanceDeserializer<'a> {
+    type Value = SceneInstance;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            INSTANCE_STRUCT,
+            &[INSTANCE_FIELD_SCENE, INSTANCE_FIELD_OVERRIDES],
+            SceneInstanceVisiter {
+                property_type_registry: self.property_type_registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum InstanceField {
+    Scene,
+    Overrides,
+}
+
+pub const INSTANCE_STRUCT: &str = "SceneInstance";
+pub const INSTANCE_FIELD_SCENE: &str = "scene";
+pub const INSTANCE_FIELD_OVERRIDES: &str = "overrides";
+
+struct SceneInstanceVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneInstanceVisiter<'a> {
+    type Value = SceneInstance;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene instance")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let scene = seq
+            .next_element::<String>()?
+            .ok_or_else(|| Error::missing_field(INSTANCE_FIELD_SCENE))?;
+        let overrides = seq
+            .next_element_seed(SceneEntityVecDeserializer {
+                property_type_registry: self.property_type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(INSTANCE_FIELD_OVERRIDES))?;
+        Ok(SceneInstance {
+            overrides,
+            ..SceneInstance::new(scene)
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut scene = None;
+        let mut overrides = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                InstanceField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_SCENE));
+                    }
+                    scene = Some(map.next_value::<String>()?);
+                }
+                InstanceField::Overrides => {
+                    if overrides.is_some() {
+                        return Err(Error::duplicate_field(INSTANCE_FIELD_OVERRIDES));
+                    }
+                    overrides = Some(map.next_value_seed(SceneEntityVecDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let scene = scene.ok_or_else(|| Error::missing_field(INSTANCE_FIELD_SCENE))?;
+        Ok(SceneInstance {
+            overrides: overrides.unwrap_or_default(),
+            ..SceneInstance::new(scene)
+        })
+    }
+}
+
 pub struct ComponentVecDeserializer<'a> {
     pub registry: &'a PropertyTypeRegistry,
 }
@@ -253,3 +660,112 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisiter<'a> {
         Ok(dynamic_properties)
     }
 }
+
+pub struct SaveGameSerializer<'a> {
+    pub save_game: &'a SaveGame,
+    pub registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a> Serialize for SaveGameSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(SAVE_GAME_STRUCT, 2)?;
+        state.serialize_field(SAVE_GAME_FIELD_VERSION, &self.save_game.version)?;
+        state.serialize_field(
+            SAVE_GAME_FIELD_SCENE,
+            &SceneSerializer::new(&self.save_game.scene, self.registry),
+        )?;
+        state.end()
+    }
+}
+
+pub struct SaveGameDeserializer<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SaveGameDeserializer<'a> {
+    type Value = SaveGame;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_GAME_STRUCT,
+            &[SAVE_GAME_FIELD_VERSION, SAVE_GAME_FIELD_SCENE],
+            SaveGameVisiter {
+                property_type_registry: self.property_type_registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveGameField {
+    Version,
+    Scene,
+}
+
+pub const SAVE_GAME_STRUCT: &str = "SaveGame";
+pub const SAVE_GAME_FIELD_VERSION: &str = "version";
+pub const SAVE_GAME_FIELD_SCENE: &str = "scene";
+
+struct SaveGameVisiter<'a> {
+    pub property_type_registry: &'a PropertyTypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SaveGameVisiter<'a> {
+    type Value = SaveGame;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("save game")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let version = seq
+            .next_element::<u32>()?
+            .ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_VERSION))?;
+        let scene = seq
+            .next_element_seed(SceneDeserializer {
+                property_type_registry: self.property_type_registry,
+            })?
+            .ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_SCENE))?;
+        Ok(SaveGame { version, scene })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut scene = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveGameField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_FIELD_VERSION));
+                    }
+                    version = Some(map.next_value::<u32>()?);
+                }
+                SaveGameField::Scene => {
+                    if scene.is_some() {
+                        return Err(Error::duplicate_field(SAVE_GAME_FIELD_SCENE));
+                    }
+                    scene = Some(map.next_value_seed(SceneDeserializer {
+                        property_type_registry: self.property_type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let version = version.ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_VERSION))?;
+        let scene = scene.ok_or_else(|| Error::missing_field(SAVE_GAME_FIELD_SCENE))?;
+        Ok(SaveGame { version, scene })
+    }
+}
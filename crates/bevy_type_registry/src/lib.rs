@@ -1,8 +1,10 @@
 mod register_type;
+mod type_data;
 mod type_registry;
 mod type_uuid;
 
 pub use register_type::*;
+pub use type_data::*;
 pub use type_registry::*;
 pub use type_uuid::*;
 pub use uuid::Uuid;
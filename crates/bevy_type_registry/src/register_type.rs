@@ -1,7 +1,8 @@
-use crate::{ComponentRegistration, ComponentRegistrationBuilder, TypeRegistry};
+use crate::{ComponentRegistration, ComponentRegistrationBuilder, TypeRegistry, TypeUuid};
 use bevy_app::AppBuilder;
-use bevy_ecs::{Component, FromResources};
+use bevy_ecs::{Component, FromResources, Resource};
 use bevy_property::{DeserializeProperty, Properties, Property};
+use std::any::Any;
 
 pub trait RegisterType {
     fn register_component<T>(&mut self) -> &mut Self
@@ -16,9 +17,40 @@ pub trait RegisterType {
     fn register_properties<T>(&mut self) -> &mut Self
     where
         T: Properties + DeserializeProperty + FromResources;
+    /// Registers a resource so that it is saved and restored along with components, for example in scenes
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource;
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + DeserializeProperty;
+    /// Serializes the registered type `T` as `name` instead of its Rust type name. See
+    /// [PropertyTypeRegistry::set_stable_name](bevy_property::PropertyTypeRegistry::set_stable_name).
+    fn register_type_name<T>(&mut self, name: &str) -> &mut Self
+    where
+        T: 'static;
+    /// Serializes the registered type `T` as its [TypeUuid]
+    fn register_type_uuid<T>(&mut self) -> &mut Self
+    where
+        T: TypeUuid + 'static;
+    /// Makes `alias`, such as an old type name, resolve to the registered type `T` when deserializing
+    fn register_type_alias<T>(&mut self, alias: &str) -> &mut Self
+    where
+        T: 'static;
+    /// Adds type-erased data for the type `T`. See [TypeDataRegistry].
+    fn register_type_data<T, D>(&mut self, data: D) -> &mut Self
+    where
+        T: 'static,
+        D: Any + Send + Sync;
+    /// Registers that `T` implements the trait `Trait`. See [TypeDataRegistry::register_trait].
+    fn register_trait<T, Trait>(
+        &mut self,
+        cast: fn(&T) -> &Trait,
+        cast_mut: fn(&mut T) -> &mut Trait,
+    ) -> &mut Self
+    where
+        T: Any,
+        Trait: ?Sized + 'static;
 }
 
 impl RegisterType for AppBuilder {
@@ -45,6 +77,18 @@ impl RegisterType for AppBuilder {
         self
     }
 
+    fn register_resource<T>(&mut self) -> &mut Self
+    where
+        T: Properties + DeserializeProperty + Resource,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.component.write().resources.register::<T>();
+            type_registry.property.write().register::<T>();
+        }
+        self
+    }
+
     fn register_property<T>(&mut self) -> &mut Self
     where
         T: Property + DeserializeProperty,
@@ -56,6 +100,66 @@ impl RegisterType for AppBuilder {
         self
     }
 
+    fn register_type_name<T>(&mut self, name: &str) -> &mut Self
+    where
+        T: 'static,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.property.write().set_stable_name::<T>(name);
+        }
+        self
+    }
+
+    fn register_type_uuid<T>(&mut self) -> &mut Self
+    where
+        T: TypeUuid + 'static,
+    {
+        self.register_type_name::<T>(&T::TYPE_UUID.to_string())
+    }
+
+    fn register_type_alias<T>(&mut self, alias: &str) -> &mut Self
+    where
+        T: 'static,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.property.write().add_alias::<T>(alias);
+        }
+        self
+    }
+
+    fn register_type_data<T, D>(&mut self, data: D) -> &mut Self
+    where
+        T: 'static,
+        D: Any + Send + Sync,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry.data.write().insert::<T, D>(data);
+        }
+        self
+    }
+
+    fn register_trait<T, Trait>(
+        &mut self,
+        cast: fn(&T) -> &Trait,
+        cast_mut: fn(&mut T) -> &mut Trait,
+    ) -> &mut Self
+    where
+        T: Any,
+        Trait: ?Sized + 'static,
+    {
+        {
+            let type_registry = self.app.resources.get::<TypeRegistry>().unwrap();
+            type_registry
+                .data
+                .write()
+                .register_trait::<T, Trait>(cast, cast_mut);
+        }
+        self
+    }
+
     fn register_component_with<T>(
         &mut self,
         build: fn(ComponentRegistrationBuilder<T>) -> ComponentRegistrationBuilder<T>,
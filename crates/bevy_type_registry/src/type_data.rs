@@ -0,0 +1,156 @@
+use bevy_property::Property;
+use bevy_utils::HashMap;
+use std::any::{Any, TypeId};
+
+/// Stores type-erased data about registered types, looked up by the [TypeId] of the type and the type of the data.
+///
+/// This is used to describe capabilities that the type registry does not know about, such as "this type implements
+/// `Debug`" (see [TypeDataRegistry::register_trait]) or "this type has a `Default`" (see [DefaultProperty]).
+#[derive(Default)]
+pub struct TypeDataRegistry {
+    data: HashMap<(TypeId, TypeId), Box<dyn Any + Send + Sync>>,
+}
+
+impl TypeDataRegistry {
+    /// Adds `data` for the type `T`, replacing any data of the same type that was already added for `T`
+    pub fn insert<T: 'static, D: Any + Send + Sync>(&mut self, data: D) {
+        self.data
+            .insert((TypeId::of::<T>(), TypeId::of::<D>()), Box::new(data));
+    }
+
+    pub fn get<D: Any>(&self, type_id: TypeId) -> Option<&D> {
+        self.data
+            .get(&(type_id, TypeId::of::<D>()))
+            .and_then(|data| data.downcast_ref::<D>())
+    }
+
+    pub fn contains<D: Any>(&self, type_id: TypeId) -> bool {
+        self.data.contains_key(&(type_id, TypeId::of::<D>()))
+    }
+
+    /// Registers that `T` implements the trait `Trait`, so that values of `T` can be accessed as `&Trait` through
+    /// [TypeDataRegistry::get_trait]. The cast functions are usually just `|value| value`:
+    /// `registry.register_trait::<Foo, dyn Debug>(|value| value, |value| value)`
+    pub fn register_trait<T: Any, Trait: ?Sized + 'static>(
+        &mut self,
+        cast: fn(&T) -> &Trait,
+        cast_mut: fn(&mut T) -> &mut Trait,
+    ) {
+        self.insert::<T, _>(TraitCast::<Trait> {
+            cast: Box::new(move |value| value.downcast_ref::<T>().map(cast)),
+            cast_mut: Box::new(move |value| value.downcast_mut::<T>().map(cast_mut)),
+        });
+    }
+
+    /// Returns `value` as a `&Trait` if its type was registered with [TypeDataRegistry::register_trait]. Note that
+    /// [DynamicProperties](bevy_property::DynamicProperties) have their own type, so they never match the type they
+    /// were created from.
+    pub fn get_trait<'a, Trait: ?Sized + 'static>(&self, value: &'a dyn Any) -> Option<&'a Trait> {
+        self.get::<TraitCast<Trait>>(value.type_id())
+            .and_then(|trait_cast| (trait_cast.cast)(value))
+    }
+
+    pub fn get_trait_mut<'a, Trait: ?Sized + 'static>(
+        &self,
+        value: &'a mut dyn Any,
+    ) -> Option<&'a mut Trait> {
+        let trait_cast = self.get::<TraitCast<Trait>>((*value).type_id())?;
+        (trait_cast.cast_mut)(value)
+    }
+
+    /// Returns `property` as a `&Trait` if its type was registered with [TypeDataRegistry::register_trait]
+    pub fn get_property_trait<'a, Trait: ?Sized + 'static>(
+        &self,
+        property: &'a dyn Property,
+    ) -> Option<&'a Trait> {
+        self.get_trait(property.any())
+    }
+
+    pub fn get_property_trait_mut<'a, Trait: ?Sized + 'static>(
+        &self,
+        property: &'a mut dyn Property,
+    ) -> Option<&'a mut Trait> {
+        self.get_trait_mut(property.any_mut())
+    }
+}
+
+type CastFn<Trait> = Box<dyn for<'a> Fn(&'a dyn Any) -> Option<&'a Trait> + Send + Sync>;
+type CastMutFn<Trait> = Box<dyn for<'a> Fn(&'a mut dyn Any) -> Option<&'a mut Trait> + Send + Sync>;
+
+/// Casts values of a registered type to `&Trait`. Added by [TypeDataRegistry::register_trait].
+pub struct TraitCast<Trait: ?Sized + 'static> {
+    cast: CastFn<Trait>,
+    cast_mut: CastMutFn<Trait>,
+}
+
+/// Type data that creates the default value of a type
+#[derive(Clone, Copy)]
+pub struct DefaultProperty {
+    create_fn: fn() -> Box<dyn Property>,
+}
+
+impl DefaultProperty {
+    pub fn of<T: Property + Default>() -> Self {
+        DefaultProperty {
+            create_fn: || Box::new(T::default()),
+        }
+    }
+
+    pub fn create(&self) -> Box<dyn Property> {
+        (self.create_fn)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DefaultProperty, TypeDataRegistry};
+    use bevy_property::{Properties, Property, PropertyVal};
+    use std::{any::TypeId, fmt::Debug};
+
+    #[derive(Properties, Default, Debug)]
+    struct Health {
+        current: f32,
+    }
+
+    trait Inspectable {
+        fn label(&self) -> String;
+        fn reset(&mut self);
+    }
+
+    impl Inspectable for Health {
+        fn label(&self) -> String {
+            format!("{} hp", self.current)
+        }
+
+        fn reset(&mut self) {
+            self.current = 100.0;
+        }
+    }
+
+    #[test]
+    fn look_up_traits_of_type_erased_values() {
+        let mut registry = TypeDataRegistry::default();
+        registry.register_trait::<Health, dyn Debug>(|value| value, |value| value);
+        registry.register_trait::<Health, dyn Inspectable>(|value| value, |value| value);
+        registry.insert::<Health, _>(DefaultProperty::of::<Health>());
+
+        let mut health = Health { current: 5.0 };
+        let property: &mut dyn Property = &mut health;
+        let debug = registry.get_property_trait::<dyn Debug>(property).unwrap();
+        assert_eq!(format!("{:?}", debug), "Health { current: 5.0 }");
+        let inspectable = registry
+            .get_property_trait_mut::<dyn Inspectable>(property)
+            .unwrap();
+        inspectable.reset();
+        assert_eq!(inspectable.label(), "100 hp");
+
+        let default = registry
+            .get::<DefaultProperty>(TypeId::of::<Health>())
+            .unwrap()
+            .create();
+        assert_eq!(default.val::<Health>().unwrap().current, 0.0);
+
+        assert!(registry.get_trait::<dyn Debug>(&5.0f32).is_none());
+        assert!(!registry.contains::<DefaultProperty>(TypeId::of::<f32>()));
+    }
+}
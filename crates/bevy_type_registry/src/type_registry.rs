@@ -1,28 +1,75 @@
+use crate::TypeDataRegistry;
 use bevy_ecs::{
     Archetype, Component, Entity, EntityMap, FromResources, MapEntities, MapEntitiesError,
-    Resources, World,
+    Resource, Resources, World,
 };
 use bevy_property::{
-    DeserializeProperty, Properties, Property, PropertyTypeRegistration, PropertyTypeRegistry,
+    DeserializeProperty, DynamicProperties, PathSegment, Properties, Property, PropertyPath,
+    PropertyPathError, PropertyTypeRegistration, PropertyTypeRegistry,
 };
 use bevy_utils::{HashMap, HashSet};
 use parking_lot::RwLock;
 use std::{any::TypeId, marker::PhantomData, sync::Arc};
+use thiserror::Error;
 
 #[derive(Clone, Default)]
 pub struct TypeRegistry {
     pub property: Arc<RwLock<PropertyTypeRegistry>>,
     pub component: Arc<RwLock<ComponentRegistry>>,
+    /// Type-erased capabilities of types, such as the traits they implement
+    pub data: Arc<RwLock<TypeDataRegistry>>,
 }
 
+/// Looks up registered types by their full or short type names. A short name shared by several types is ambiguous, so
+/// those types can only be looked up by their full names.
 #[derive(Default)]
-pub struct ComponentRegistry {
-    pub registrations: HashMap<TypeId, ComponentRegistration>,
+pub struct TypeNames {
     pub short_names: HashMap<String, TypeId>,
     pub full_names: HashMap<String, TypeId>,
     pub ambigous_names: HashSet<String>,
 }
 
+impl TypeNames {
+    pub fn add(&mut self, short_name: &str, full_name: &str, ty: TypeId) {
+        self.full_names.insert(full_name.to_string(), ty);
+        if self.short_names.contains_key(short_name) || self.ambigous_names.contains(short_name) {
+            // name is ambiguous. fall back to long names for all ambiguous types
+            self.short_names.remove(short_name);
+            self.ambigous_names.insert(short_name.to_string());
+        } else {
+            self.short_names.insert(short_name.to_string(), ty);
+        }
+    }
+
+    pub fn get_with_full_name(&self, full_name: &str) -> Option<TypeId> {
+        self.full_names.get(full_name).copied()
+    }
+
+    pub fn get_with_short_name(&self, short_name: &str) -> Option<TypeId> {
+        self.short_names.get(short_name).copied()
+    }
+
+    /// Looks up a type by its short name, then by its full name. Panics if `type_name` is an ambiguous short name.
+    pub fn get_with_name(&self, type_name: &str) -> Option<TypeId> {
+        let mut ty = self.get_with_short_name(type_name);
+        if ty.is_none() {
+            ty = self.get_with_full_name(type_name);
+            if ty.is_none() && self.ambigous_names.contains(type_name) {
+                panic!("Type name is ambiguous: {}", type_name);
+            }
+        }
+        ty
+    }
+}
+
+#[derive(Default)]
+pub struct ComponentRegistry {
+    pub registrations: HashMap<TypeId, ComponentRegistration>,
+    pub names: TypeNames,
+    /// Resources that are saved and restored along with components, for example in scenes
+    pub resources: ResourceRegistry,
+}
+
 impl ComponentRegistry {
     pub fn register<T>(&mut self)
     where
@@ -32,16 +79,11 @@ impl ComponentRegistry {
     }
 
     pub fn add_registration(&mut self, registration: ComponentRegistration) {
-        let short_name = registration.short_name.to_string();
-        self.full_names
-            .insert(registration.long_name.to_string(), registration.ty);
-        if self.short_names.contains_key(&short_name) || self.ambigous_names.contains(&short_name) {
-            // name is ambiguous. fall back to long names for all ambiguous types
-            self.short_names.remove(&short_name);
-            self.ambigous_names.insert(short_name);
-        } else {
-            self.short_names.insert(short_name, registration.ty);
-        }
+        self.names.add(
+            &registration.short_name,
+            registration.long_name,
+            registration.ty,
+        );
         self.registrations.insert(registration.ty, registration);
     }
 
@@ -50,31 +92,85 @@ impl ComponentRegistry {
     }
 
     pub fn get_with_full_name(&self, full_name: &str) -> Option<&ComponentRegistration> {
-        self.full_names
-            .get(full_name)
-            .and_then(|id| self.registrations.get(id))
+        self.names
+            .get_with_full_name(full_name)
+            .and_then(|id| self.registrations.get(&id))
     }
 
     pub fn get_with_short_name(&self, short_name: &str) -> Option<&ComponentRegistration> {
-        self.short_names
-            .get(short_name)
-            .and_then(|id| self.registrations.get(id))
+        self.names
+            .get_with_short_name(short_name)
+            .and_then(|id| self.registrations.get(&id))
     }
 
     pub fn get_with_name(&self, type_name: &str) -> Option<&ComponentRegistration> {
-        let mut registration = self.get_with_short_name(type_name);
-        if registration.is_none() {
-            registration = self.get_with_full_name(type_name);
-            if registration.is_none() && self.ambigous_names.contains(type_name) {
-                panic!("Type name is ambiguous: {}", type_name);
-            }
-        }
-        registration
+        self.names
+            .get_with_name(type_name)
+            .and_then(|id| self.registrations.get(&id))
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &ComponentRegistration> {
         self.registrations.values()
     }
+
+    /// Splits a path such as `Transform.translation.x` into the registration of its component and the path within it
+    fn get_with_path(
+        &self,
+        path: &str,
+    ) -> Result<(&ComponentRegistration, PropertyPath), ComponentPathError> {
+        let mut path = PropertyPath::parse(path)?;
+        let component_name = match path.segments.remove(0) {
+            PathSegment::Field(name) => name,
+            PathSegment::Index(_) => return Err(ComponentPathError::MissingComponentName),
+        };
+        let registration = self.get_with_name(&component_name).ok_or(
+            ComponentPathError::UnregisteredComponent {
+                name: component_name,
+            },
+        )?;
+        Ok((registration, path))
+    }
+
+    /// Sets the property at `path` in a component of the given entity. The path starts with the name of the
+    /// component, for example `Transform.translation.x`.
+    pub fn set_entity_path(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        path: &str,
+        value: &dyn Property,
+    ) -> Result<(), ComponentPathError> {
+        let (registration, path) = self.get_with_path(path)?;
+        registration.set_entity_path(world, entity, &path, value)
+    }
+
+    /// Parses `value` as RON and sets the property at `path` in a component of the given entity. See
+    /// [ComponentRegistry::set_entity_path] and [PropertyPath::set_ron].
+    pub fn set_entity_path_ron(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        path: &str,
+        value: &str,
+        property_type_registry: &PropertyTypeRegistry,
+    ) -> Result<(), ComponentPathError> {
+        let (registration, path) = self.get_with_path(path)?;
+        registration.set_entity_path_ron(world, entity, &path, value, property_type_registry)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ComponentPathError {
+    #[error("Entity does not exist.")]
+    NoSuchEntity,
+    #[error("Entity does not have a {name} component.")]
+    MissingComponent { name: String },
+    #[error("Component paths must start with the name of a component.")]
+    MissingComponentName,
+    #[error("Component {name} is not registered.")]
+    UnregisteredComponent { name: String },
+    #[error(transparent)]
+    Path(#[from] PropertyPathError),
 }
 
 #[derive(Clone)]
@@ -199,6 +295,68 @@ impl ComponentRegistration {
         (self.component_properties_fn)(archetype, entity_index)
     }
 
+    /// Returns the properties of this entity's component, or `None` if the entity doesn't exist or doesn't have the
+    /// component
+    pub fn get_entity_properties<'a>(
+        &self,
+        world: &'a World,
+        entity: Entity,
+    ) -> Option<&'a dyn Properties> {
+        let location = world.get_entity_location(entity)?;
+        if !world.has_component_type(entity, self.ty) {
+            return None;
+        }
+
+        let archetype = world.archetypes().nth(location.archetype as usize).unwrap();
+        Some(self.get_component_properties(archetype, location.index))
+    }
+
+    /// Sets the property at `path` in this entity's component. The component is patched through
+    /// [ComponentRegistration::apply_property_to_entity], so it is marked as mutated.
+    pub fn set_entity_path(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        path: &PropertyPath,
+        value: &dyn Property,
+    ) -> Result<(), ComponentPathError> {
+        self.edit_entity_component(world, entity, |properties| path.set(properties, value))
+    }
+
+    /// Parses `value` as RON and sets the property at `path` in this entity's component. See [PropertyPath::set_ron].
+    pub fn set_entity_path_ron(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        path: &PropertyPath,
+        value: &str,
+        property_type_registry: &PropertyTypeRegistry,
+    ) -> Result<(), ComponentPathError> {
+        self.edit_entity_component(world, entity, |properties| {
+            path.set_ron(properties, value, property_type_registry)
+        })
+    }
+
+    fn edit_entity_component(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        edit: impl FnOnce(&mut DynamicProperties) -> Result<(), PropertyPathError>,
+    ) -> Result<(), ComponentPathError> {
+        if world.get_entity_location(entity).is_none() {
+            return Err(ComponentPathError::NoSuchEntity);
+        }
+        let mut properties = self
+            .get_entity_properties(world, entity)
+            .ok_or_else(|| ComponentPathError::MissingComponent {
+                name: self.short_name.clone(),
+            })?
+            .to_dynamic();
+        edit(&mut properties)?;
+        self.apply_property_to_entity(world, entity, &properties);
+        Ok(())
+    }
+
     pub fn component_copy(
         &self,
         source_world: &World,
@@ -328,6 +486,175 @@ where
     }
 }
 
+#[derive(Default)]
+pub struct ResourceRegistry {
+    pub registrations: HashMap<TypeId, ResourceRegistration>,
+    pub names: TypeNames,
+}
+
+impl ResourceRegistry {
+    pub fn register<T>(&mut self)
+    where
+        T: Properties + Resource,
+    {
+        self.add_registration(ResourceRegistration::of::<T>());
+    }
+
+    pub fn add_registration(&mut self, registration: ResourceRegistration) {
+        self.names.add(
+            &registration.short_name,
+            registration.long_name,
+            registration.ty,
+        );
+        self.registrations.insert(registration.ty, registration);
+    }
+
+    pub fn get(&self, type_id: &TypeId) -> Option<&ResourceRegistration> {
+        self.registrations.get(type_id)
+    }
+
+    pub fn get_with_full_name(&self, full_name: &str) -> Option<&ResourceRegistration> {
+        self.names
+            .get_with_full_name(full_name)
+            .and_then(|id| self.registrations.get(&id))
+    }
+
+    pub fn get_with_short_name(&self, short_name: &str) -> Option<&ResourceRegistration> {
+        self.names
+            .get_with_short_name(short_name)
+            .and_then(|id| self.registrations.get(&id))
+    }
+
+    pub fn get_with_name(&self, type_name: &str) -> Option<&ResourceRegistration> {
+        self.names
+            .get_with_name(type_name)
+            .and_then(|id| self.registrations.get(&id))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ResourceRegistration> {
+        self.registrations.values()
+    }
+}
+
+#[derive(Clone)]
+pub struct ResourceRegistration {
+    pub ty: TypeId,
+    pub short_name: String,
+    pub long_name: &'static str,
+    resource_properties_fn: fn(&Resources) -> Option<DynamicProperties>,
+    resource_apply_fn: fn(&Resources, &dyn Property) -> bool,
+}
+
+impl ResourceRegistration {
+    pub fn of<T: Properties + Resource>() -> Self {
+        Self {
+            ty: TypeId::of::<T>(),
+            resource_properties_fn: |resources: &Resources| {
+                resources.get::<T>().map(|resource| resource.to_dynamic())
+            },
+            resource_apply_fn: |resources: &Resources, property: &dyn Property| {
+                if let Some(mut resource) = resources.get_mut::<T>() {
+                    resource.apply(property);
+                    true
+                } else {
+                    false
+                }
+            },
+            short_name: PropertyTypeRegistration::get_short_name(std::any::type_name::<T>()),
+            long_name: std::any::type_name::<T>(),
+        }
+    }
+
+    /// Returns the properties of the resource, or `None` if it has not been inserted
+    pub fn get_resource_properties(&self, resources: &Resources) -> Option<DynamicProperties> {
+        (self.resource_properties_fn)(resources)
+    }
+
+    /// Applies the given property to the resource. Returns false if the resource has not been inserted.
+    pub fn apply_property_to_resource(
+        &self,
+        resources: &Resources,
+        property: &dyn Property,
+    ) -> bool {
+        (self.resource_apply_fn)(resources, property)
+    }
+}
+
 pub trait IntoComponent<ToComponent: Component> {
     fn into_component(&self, resources: &Resources) -> ToComponent;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentPathError, ComponentRegistry, TypeNames};
+    use bevy_ecs::World;
+    use bevy_math::Vec3;
+    use bevy_property::{Properties, PropertyTypeRegistry};
+
+    #[derive(Properties, Default)]
+    struct Margin {
+        left: f32,
+    }
+
+    #[derive(Properties, Default)]
+    struct Style {
+        translation: Vec3,
+        margin: Margin,
+    }
+
+    #[test]
+    fn set_component_fields_by_path() {
+        let mut registry = ComponentRegistry::default();
+        registry.register::<Style>();
+        let mut world = World::default();
+        let entity = world.spawn((Style::default(),));
+
+        registry
+            .set_entity_path(&mut world, entity, "Style.translation.y", &2.0f32)
+            .unwrap();
+        registry
+            .set_entity_path_ron(
+                &mut world,
+                entity,
+                "Style.margin.left",
+                "3.5",
+                &PropertyTypeRegistry::default(),
+            )
+            .unwrap();
+        let style = world.get::<Style>(entity).unwrap();
+        assert_eq!(style.translation, Vec3::new(0.0, 2.0, 0.0));
+        assert_eq!(style.margin.left, 3.5);
+
+        assert!(matches!(
+            registry.set_entity_path(&mut world, entity, "Style.margin.right", &1.0f32),
+            Err(ComponentPathError::Path(_))
+        ));
+        assert!(matches!(
+            registry.set_entity_path(&mut world, entity, "Transform.scale", &1.0f32),
+            Err(ComponentPathError::UnregisteredComponent { .. })
+        ));
+        let other = world.spawn((0u32,));
+        assert!(matches!(
+            registry.set_entity_path(&mut world, other, "Style.margin.left", &1.0f32),
+            Err(ComponentPathError::MissingComponent { .. })
+        ));
+    }
+
+    #[test]
+    fn ambiguous_short_names_need_full_names() {
+        struct A;
+        struct B;
+        let mut names = TypeNames::default();
+        names.add("Margin", "ui::Margin", std::any::TypeId::of::<A>());
+        assert_eq!(
+            names.get_with_name("Margin"),
+            Some(std::any::TypeId::of::<A>())
+        );
+        names.add("Margin", "layout::Margin", std::any::TypeId::of::<B>());
+        assert_eq!(names.get_with_short_name("Margin"), None);
+        assert_eq!(
+            names.get_with_name("layout::Margin"),
+            Some(std::any::TypeId::of::<B>())
+        );
+    }
+}
@@ -21,9 +21,9 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
     // You should see the changes immediately show up in your app.
 
     // Add entities to the world:
+    // mesh
+    commands.spawn_scene(scene_handle);
     commands
-        // mesh
-        .spawn_scene(scene_handle)
         // light
         .spawn(LightComponents {
             transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),
@@ -16,6 +16,7 @@ fn main() {
         .register_property::<Test>()
         .register_property::<Nested>()
         .register_property::<CustomProperty>()
+        .register_property::<Mood>()
         .add_startup_system(setup.system())
         .run();
 }
@@ -32,6 +33,15 @@ pub struct Nested {
     b: usize,
 }
 
+// Enums can derive Properties too. Their fields are the fields of the active variant.
+// Switching to another variant starts from default values, so the fields must implement Default.
+#[derive(Properties)]
+pub enum Mood {
+    Calm,
+    Angry(usize),
+    Scared { threat: String },
+}
+
 #[derive(Serialize, Deserialize, Default, Clone, Property)]
 pub struct CustomProperty {
     a: usize,
@@ -54,6 +64,11 @@ fn setup(type_registry: Res<TypeRegistry>) {
     test.set_prop("a", &x);
     assert_eq!(test.a, 3);
 
+    // Nested properties can be accessed with paths. Sequence items are accessed with indices like "items[0]"
+    test.set_path("nested.b", &9usize).unwrap();
+    assert_eq!(test.nested.b, 9);
+    assert_eq!(*test.path("nested.b").unwrap().val::<usize>().unwrap(), 9);
+
     // DynamicProperties also implements the Properties trait.
     let mut patch = DynamicProperties::map();
     patch.set::<usize>("a", 4);
@@ -92,6 +107,20 @@ fn setup(type_registry: Res<TypeRegistry>) {
     let dynamic_properties = deserialize_dynamic_properties(&ron_string, &registry).unwrap();
     let round_tripped = serialize_property(&dynamic_properties, &registry);
     assert_eq!(ron_string, round_tripped);
+
+    // Enums are patched with the variant name and the fields of that variant
+    let mut mood = Mood::Calm;
+    let mut patch = DynamicProperties::enum_variant("Angry");
+    patch.push(Box::new(5usize), None);
+    mood.apply(&patch);
+    assert_eq!(mood.variant_name(), Some("Angry"));
+    assert_eq!(*mood.prop_with_index(0).unwrap().val::<usize>().unwrap(), 5);
+
+    let ron_string = serialize_property(&mood, &registry);
+    println!("{}\n", ron_string);
+    let dynamic_properties = deserialize_dynamic_properties(&ron_string, &registry).unwrap();
+    let round_tripped = serialize_property(&dynamic_properties, &registry);
+    assert_eq!(ron_string, round_tripped);
 }
 
 fn serialize_property<T>(property: &T, registry: &PropertyTypeRegistry) -> String
@@ -15,6 +15,7 @@ fn main() {
         .add_startup_system(load_scene_system.system())
         .add_startup_system(infotext_system.system())
         .add_system(print_system.system())
+        .add_system(save_on_key_system.system())
         .run();
 }
 
@@ -88,8 +89,9 @@ fn save_scene_system(_world: &mut World, resources: &mut Resources) {
     world.spawn((ComponentA { x: 3.0, y: 4.0 },));
 
     // The component registry resource contains information about all registered components. This is used to construct scenes.
+    // Resources registered with `register_resource` are captured from `resources` as well.
     let type_registry = resources.get::<TypeRegistry>().unwrap();
-    let scene = DynamicScene::from_world(&world, &type_registry.component.read());
+    let scene = DynamicScene::from_world(&world, resources, &type_registry.component.read());
 
     // Scenes can be serialized like this:
     println!(
@@ -97,7 +99,30 @@ fn save_scene_system(_world: &mut World, resources: &mut Resources) {
         scene.serialize_ron(&type_registry.property.read()).unwrap()
     );
 
-    // TODO: save scene
+    // Scenes can also be saved as assets. We keep this one around so that `save_on_key_system` can save it later.
+    let scene_handle = resources
+        .get_mut::<Assets<DynamicScene>>()
+        .unwrap()
+        .add(scene);
+    drop(type_registry);
+    resources.insert(SavedScene(scene_handle));
+}
+
+struct SavedScene(Handle<DynamicScene>);
+
+// Pressing S writes the saved scene as RON to "assets/scenes/saved_scene.scn". Saving only happens on request so that
+// running the example does not modify the assets folder.
+fn save_on_key_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    saved_scene: Res<SavedScene>,
+) {
+    if keyboard_input.just_pressed(KeyCode::S) {
+        asset_server
+            .save(&saved_scene.0, "scenes/saved_scene.scn")
+            .unwrap();
+        println!("Saved the scene to assets/scenes/saved_scene.scn");
+    }
 }
 
 // This is only necessary for the info message in the UI. See examples/ui/text.rs for a standalone text example.
@@ -110,7 +135,7 @@ fn infotext_system(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ..Default::default()
             },
             text: Text {
-                value: "Nothing to see in this window! Check the console output!".to_string(),
+                value: "Check the console output! Press S to save the scene.".to_string(),
                 font: asset_server.load("fonts/FiraSans-Bold.ttf"),
                 style: TextStyle {
                     font_size: 50.0,
@@ -18,6 +18,7 @@ fn main() {
     App::build()
         .add_resource(AssetServerSettings {
             asset_folder: "/".to_string(),
+            ..Default::default()
         })
         .add_default_plugins()
         .add_asset::<RustSourceCode>()
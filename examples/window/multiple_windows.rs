@@ -153,8 +153,8 @@ fn setup(
     // SETUP SCENE
 
     // add entities to the world
+    commands.spawn_scene(asset_server.load("models/monkey/Monkey.gltf"));
     commands
-        .spawn_scene(asset_server.load("models/monkey/Monkey.gltf"))
         // light
         .spawn(LightComponents {
             transform: Transform::from_translation(Vec3::new(4.0, 5.0, 4.0)),